@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Parser, Subcommand};
+
+use crate::output::OutputFormat;
+use crate::session::state;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -17,12 +20,21 @@ EXAMPLES:\n  \
   getapi list                 List all available providers\n  \
   getapi list --search email  Search for providers\n  \
   getapi resume twitter       Resume a paused session\n  \
+  getapi twitter --skip create_project  Skip a step you've already done\n  \
   getapi status               Show setup progress for all providers\n  \
-  getapi validate twitter     Re-validate existing credentials\n\n\
+  getapi validate twitter     Re-validate existing credentials\n  \
+  getapi validate --all --json  Check every provider set up in this project\n  \
+  getapi validate --all --max-age 24h  Skip credentials that passed in the last day\n  \
+  getapi twitter --answers answers.json  Run without prompts (CI)\n  \
+  getapi test-recipe twitter --case cases/*.json  Test a recipe against scripted cases\n\n\
 SESSIONS:\n  \
   Progress is saved automatically to .getapi/sessions/. Credentials are NEVER stored\n  \
-  in sessions — only progress metadata. On resume, getapi checks your output file\n  \
-  (.env by default) to detect already-collected credentials.\n\n\
+  in sessions — only progress metadata. A paused setup keeps its credentials in\n  \
+  .getapi/pending/ (readable only by you) until it finishes; --stage-credentials keeps\n  \
+  them there as soon as they're pasted. When each credential was last validated is kept\n  \
+  in .getapi/state.json, with a fingerprint (never the value). getapi writes a .gitignore\n  \
+  in .getapi/ for all of these. On resume, getapi checks your output file (.env by\n  \
+  default) to detect already-collected credentials.\n\n\
 OUTPUT:\n  \
   By default, credentials are written to .env in the current directory.\n  \
   Use --output to change format (env, json, stdout).\n  \
@@ -38,7 +50,7 @@ pub struct Cli {
 
     /// Output format for collected credentials
     #[arg(long, short, default_value = "env", value_enum)]
-    pub output: OutputFormat,
+    pub output: Format,
 
     /// Output file path (default: .env for env format, credentials.json for json)
     #[arg(long, value_name = "PATH")]
@@ -48,6 +60,48 @@ pub struct Cli {
     #[arg(long)]
     pub non_interactive: bool,
 
+    /// Take credentials from environment variables when their output key is already set
+    #[arg(long)]
+    pub use_env: bool,
+
+    /// Run without prompts, taking every answer from a JSON answers file
+    #[arg(long, value_name = "PATH", conflicts_with = "non_interactive")]
+    pub answers: Option<String>,
+
+    /// Fail instead of saving credentials that didn't validate
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Stage each credential in .getapi/pending/ as soon as it's collected, so a crash doesn't
+    /// lose it
+    #[arg(long)]
+    pub stage_credentials: bool,
+
+    /// Skip a step marked skippable in the recipe (repeatable)
+    #[arg(long = "skip", value_name = "STEP")]
+    pub skip: Vec<String>,
+
+    /// Use a full-screen interface with a step sidebar (falls back to line mode without a TTY)
+    #[arg(long, conflicts_with_all = ["non_interactive", "answers"])]
+    pub tui: bool,
+
+    /// Log each HTTP request and response to stderr, with secrets redacted (or set GETAPI_LOG)
+    #[arg(long, short, global = true)]
+    pub verbose: bool,
+
+    /// Record validator HTTP traffic as redacted cassettes in this directory
+    #[arg(
+        long,
+        value_name = "DIR",
+        global = true,
+        conflicts_with = "replay_http"
+    )]
+    pub record_http: Option<String>,
+
+    /// Replay validator HTTP traffic from cassettes in this directory instead of the network
+    #[arg(long, value_name = "DIR", global = true)]
+    pub replay_http: Option<String>,
+
     /// Use a custom recipe file instead of a bundled provider
     #[arg(long, value_name = "PATH")]
     pub recipe: Option<String>,
@@ -63,6 +117,10 @@ pub enum Command {
     Resume {
         /// Provider to resume
         provider: String,
+
+        /// Go back to a specific step, such as one skipped earlier
+        #[arg(long, value_name = "STEP")]
+        step: Option<String>,
     },
 
     /// Show setup progress for all providers
@@ -79,10 +137,22 @@ pub enum Command {
         category: Option<String>,
     },
 
-    /// Re-run credential validation for a provider
+    /// Re-run credential validation for a provider, or for every provider set up here
     Validate {
-        /// Provider to validate
-        provider: String,
+        /// Provider to validate (omit to validate every provider with a session or in the manifest)
+        provider: Option<String>,
+
+        /// Validate every provider with a session or in the manifest
+        #[arg(long, conflicts_with = "provider")]
+        all: bool,
+
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Don't re-check credentials that passed more recently than this (e.g. 24h, 30m, 7d)
+        #[arg(long, value_name = "AGE", value_parser = state::parse_age)]
+        max_age: Option<chrono::Duration>,
     },
 
     /// Clear session data and start fresh
@@ -93,11 +163,57 @@ pub enum Command {
 
     /// Update provider recipes from the remote repository
     Update,
+
+    /// Run a recipe headlessly against scripted test cases
+    TestRecipe {
+        /// Provider id or path to a recipe file
+        recipe: String,
+
+        /// Test case files (JSON) with answers, mock HTTP responses and expectations
+        #[arg(long = "case", value_name = "PATH", num_args = 1.., required = true)]
+        cases: Vec<String>,
+    },
+
+    /// Set up a provider from a page in your browser
+    Web {
+        /// Provider name (e.g. figma)
+        provider: String,
+
+        /// Print the page's URL without opening a browser
+        #[arg(long)]
+        no_open: bool,
+    },
+
+    /// Serve recipes to an editor or other program over JSON-RPC
+    Serve {
+        /// Speak newline-delimited JSON-RPC 2.0 on stdin/stdout
+        #[arg(long, required = true)]
+        stdio: bool,
+    },
+
+    /// Clear the clipboard after a delay if it still holds a value (internal helper)
+    #[command(name = "__clear-clipboard", hide = true)]
+    ClearClipboard {
+        #[arg(long)]
+        after: u64,
+    },
 }
 
-#[derive(ValueEnum, Clone, Debug, PartialEq)]
-pub enum OutputFormat {
+/// The `--output` values, kept apart from [`OutputFormat`] so its public type carries no clap
+/// derive.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Format {
     Env,
     Json,
     Stdout,
 }
+
+impl From<Format> for OutputFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Env => OutputFormat::Env,
+            Format::Json => OutputFormat::Json,
+            Format::Stdout => OutputFormat::Stdout,
+        }
+    }
+}
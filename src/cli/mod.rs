@@ -1 +1,906 @@
-pub mod args;
+//! The `getapi` command line. The binary is a thin wrapper around [`main`]; none of this is
+//! part of the library API.
+
+mod args;
+
+use std::collections::HashMap;
+use std::io::IsTerminal;
+
+use clap::Parser;
+
+use crate::error::{GetapiError, Result};
+use crate::frontend::answers::Answers;
+use crate::frontend::scripted::ScriptedFrontend;
+use crate::frontend::terminal::TerminalFrontend;
+use crate::frontend::tui::TuiFrontend;
+use crate::frontend::{Frontend, Message};
+use crate::output::env as env_output;
+use crate::output::OutputFormat;
+use crate::recipe::registry::RecipeRegistry;
+use crate::recipe::types::Recipe;
+use crate::rpc::types::RunParams;
+use crate::runner::batch::Status;
+use crate::runner::context::RunContext;
+use crate::runner::steps::validate::SavedCheck;
+use crate::session::state::Freshness;
+use crate::session::types::{Session, SessionStatus};
+use crate::validators::ValidationRecord;
+use crate::{clipboard, harness, http, interrupt, manifest, recipe, rpc, runner, session, ui, web};
+
+use args::{Cli, Command, Format};
+
+/// Parse the command line and run it, exiting with the error's code on failure.
+pub fn main() {
+    let cli = Cli::parse();
+
+    if let Err(e) = run(cli) {
+        if !matches!(e, GetapiError::UserCancelled | GetapiError::Paused) {
+            eprintln!("\n  {} {}", console::style("Error:").red().bold(), e);
+        }
+        std::process::exit(e.exit_code());
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let mut registry = RecipeRegistry::new();
+    if let Some(ref dir) = cli.recipe_dir {
+        registry = registry.with_extra_dir(dir);
+    }
+
+    if cli.verbose {
+        http::log::set_verbose(true);
+    }
+    if let Some(ref dir) = cli.record_http {
+        http::set_mode(http::Mode::Record(dir.into()));
+    } else if let Some(ref dir) = cli.replay_http {
+        http::set_mode(http::Mode::Replay(dir.into()));
+    }
+
+    match &cli.command {
+        Some(Command::Update) => cmd_update(),
+        Some(Command::ClearClipboard { after }) => {
+            if let Ok(fingerprint) = std::env::var(clipboard::FINGERPRINT_VAR) {
+                clipboard::clear_after(*after, &fingerprint);
+            }
+            Ok(())
+        }
+        Some(Command::List { search, category }) => {
+            cmd_list(&registry, search.clone(), category.clone())
+        }
+        Some(Command::Status) => cmd_status(&registry),
+        Some(Command::Resume { provider, step }) => {
+            cmd_resume(&registry, provider, step.as_deref(), &cli)
+        }
+        Some(Command::Validate {
+            provider: Some(provider),
+            json: false,
+            max_age,
+            ..
+        }) => cmd_validate(&registry, provider, *max_age),
+        Some(Command::Validate {
+            provider,
+            json,
+            max_age,
+            ..
+        }) => cmd_validate_all(&registry, provider.as_deref(), *json, *max_age),
+        Some(Command::Reset { provider }) => cmd_reset(provider.clone()),
+        Some(Command::TestRecipe { recipe, cases }) => cmd_test_recipe(&registry, recipe, cases),
+        Some(Command::Web { provider, no_open }) => {
+            let params = RunParams {
+                provider: provider.clone(),
+                output: Some(cli.output.into()),
+                output_file: cli.output_file.clone(),
+                use_env: use_env(&cli),
+            };
+            web::run(&registry, params, !no_open)
+        }
+        Some(Command::Serve { .. }) => rpc::serve(
+            &registry,
+            Box::new(std::io::stdin().lock()),
+            Box::new(std::io::stdout()),
+        ),
+        None => {
+            if let Some(ref recipe_path) = cli.recipe {
+                let recipe = recipe::loader::load_from_file(recipe_path)?;
+                run_provider_setup(&recipe, &cli)
+            } else if cli.providers.is_empty() {
+                cmd_manifest(&registry, &cli)
+            } else {
+                for provider_name in &cli.providers {
+                    let recipe = registry
+                        .find(provider_name)
+                        .ok_or_else(|| GetapiError::ProviderNotFound(provider_name.clone()))?
+                        .clone();
+                    run_provider_setup(&recipe, &cli)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn run_provider_setup(recipe: &Recipe, cli: &Cli) -> Result<()> {
+    let output_file = cli
+        .output_file
+        .clone()
+        .unwrap_or_else(|| OutputFormat::from(cli.output).default_file().to_string());
+
+    let mut frontend = frontend(cli, recipe, &[])?;
+
+    // Check if credentials already exist (UAC-12)
+    if !cli.non_interactive && cli.answers.is_none() {
+        let existing = env_output::read_existing(&output_file);
+        let all_present = recipe.outputs.iter().all(|o| existing.contains_key(&o.key));
+
+        if all_present && !recipe.outputs.is_empty() {
+            frontend.print(Message::Header {
+                name: &recipe.display_name,
+                description: &recipe.description,
+            });
+            frontend.print(Message::Blank);
+            frontend
+                .success("All credentials for this provider already exist in your output file.");
+            frontend.print(Message::Blank);
+            let keys: Vec<&str> = recipe.outputs.iter().map(|o| o.key.as_str()).collect();
+            frontend.info(&format!("Found: {}", keys.join(", ")));
+            frontend.print(Message::Blank);
+
+            let choice = frontend.select(
+                "existing_credentials",
+                "What would you like to do?",
+                &[
+                    "Validate existing credentials",
+                    "Re-run setup (will overwrite)",
+                    "Exit",
+                ],
+                0,
+            )?;
+
+            match choice {
+                0 => {
+                    // The summary table is printed straight to the terminal
+                    drop(frontend);
+                    return validate_saved(recipe, &output_file, &existing, None);
+                }
+                1 => { /* continue with setup */ }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    let mut ctx = context(cli, &output_file, frontend);
+
+    let mut session = Session::new(
+        &recipe.id,
+        &output_file,
+        &format!("{:?}", cli.output).to_lowercase(),
+    );
+    session.recipe_version = recipe.version.clone();
+    ctx.checkpoint = Some(session::store::checkpoint(&session, recipe));
+
+    let result = runner::run(recipe, &mut ctx);
+    session::store::save_run(&mut session, recipe, &mut ctx, result)
+}
+
+fn cmd_resume(
+    registry: &RecipeRegistry,
+    provider: &str,
+    step: Option<&str>,
+    cli: &Cli,
+) -> Result<()> {
+    let session = session::store::load(provider)?;
+
+    let recipe = registry
+        .find(provider)
+        .ok_or_else(|| GetapiError::ProviderNotFound(provider.to_string()))?
+        .clone();
+
+    let output_file = cli
+        .output_file
+        .clone()
+        .unwrap_or(session.output_file.clone());
+
+    let mut ctx = context(
+        cli,
+        &output_file,
+        frontend(cli, &recipe, &session.completed_steps)?,
+    );
+    ctx.frontend
+        .info(&format!("Resuming {} setup...", recipe.display_name));
+    ctx.frontend.print(Message::Blank);
+
+    // Restore session state
+    ctx.completed_steps = session.completed_steps.clone();
+    ctx.skipped_steps = session.skipped_steps.clone();
+    ctx.choices_made = session.choices_made.clone();
+
+    // Restore template vars from choices
+    for (step_id, chosen_label) in &session.choices_made {
+        if let Some(recipe::types::Step::PromptChoice { choices, .. }) =
+            recipe.steps.iter().find(|s| s.id() == step_id)
+        {
+            if let Some(chosen) = choices.iter().find(|c| &c.label == chosen_label) {
+                if let Some(ref sets) = chosen.sets {
+                    for (k, v) in sets {
+                        ctx.set_var(k.clone(), v.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    // Check .env for already-collected values, then values staged by an unfinished run
+    let existing = env_output::read_existing(&output_file);
+    for output_def in &recipe.outputs {
+        if let Some(value) = existing.get(&output_def.key) {
+            if output_def.sensitive {
+                ctx.set_secret(output_def.key.clone(), value.clone());
+            } else {
+                ctx.set_collected(output_def.key.clone(), value.clone());
+            }
+        }
+    }
+    let pending = session::pending::load(&recipe.id);
+    if !pending.is_empty() {
+        let mut keys: Vec<&str> = pending.keys().map(String::as_str).collect();
+        keys.sort();
+        ctx.frontend
+            .info(&format!("Picked up from last time: {}", keys.join(", ")));
+    }
+    for (key, value) in pending {
+        if recipe.is_sensitive(&key) {
+            ctx.set_secret(key, value);
+        } else {
+            ctx.set_collected(key, value);
+        }
+    }
+
+    // Find resume point: the requested step, or where the session paused
+    if let Some(step) = step {
+        let idx = recipe
+            .find_step_index(step)
+            .ok_or_else(|| GetapiError::StepNotFound(step.to_string()))?;
+        ctx.completed_steps.retain(|id| id != step);
+        ctx.skipped_steps.retain(|id| id != step);
+        ctx.current_step_index = idx;
+    } else if let Some(ref current) = session.current_step {
+        if let Some(idx) = recipe.find_step_index(current) {
+            ctx.current_step_index = idx;
+        }
+    }
+
+    ctx.checkpoint = Some(session::store::checkpoint(&session, &recipe));
+
+    let result = runner::run(&recipe, &mut ctx);
+    let mut updated_session = session;
+    session::store::save_run(&mut updated_session, &recipe, &mut ctx, result)
+}
+
+fn cmd_validate(
+    registry: &RecipeRegistry,
+    provider: &str,
+    max_age: Option<chrono::Duration>,
+) -> Result<()> {
+    let recipe = registry
+        .find(provider)
+        .ok_or_else(|| GetapiError::ProviderNotFound(provider.to_string()))?;
+
+    let output_file = session::store::load(provider)
+        .map(|s| s.output_file)
+        .unwrap_or_else(|_| ".env".to_string());
+
+    let existing = env_output::read_existing(&output_file);
+
+    let missing: Vec<&str> = recipe
+        .outputs
+        .iter()
+        .filter(|o| !existing.contains_key(&o.key))
+        .map(|o| o.key.as_str())
+        .collect();
+    if missing.len() == recipe.outputs.len() && !missing.is_empty() {
+        ui::print_info(&format!("Run `getapi {}` to set them up.", provider));
+        return Err(GetapiError::MissingCredentials(format!(
+            "{} in {}",
+            missing.join(", "),
+            output_file
+        )));
+    }
+
+    validate_saved(recipe, &output_file, &existing, max_age)
+}
+
+/// Run every validate step whose dependencies are saved in `output_file`, print a summary
+/// table, and fail unless they all pass. With `max_age`, checks that passed more recently than
+/// that aren't run again.
+fn validate_saved(
+    recipe: &Recipe,
+    output_file: &str,
+    existing: &HashMap<String, String>,
+    max_age: Option<chrono::Duration>,
+) -> Result<()> {
+    let state = session::state::load();
+    let entries = state.providers.get(&recipe.id);
+    let checks = runner::steps::validate::check_saved(recipe, existing, entries.zip(max_age))?;
+    record_validations(recipe, &checks, existing);
+    if checks.is_empty() {
+        ui::print_info("No validation step defined for this provider.");
+        return Ok(());
+    }
+
+    println!();
+    let rows: Vec<Vec<String>> = checks
+        .iter()
+        .map(|check| match check {
+            SavedCheck::Ran(record) => vec![
+                format!("{} ({})", record.step_id, record.method),
+                if record.passed {
+                    console::style("✓ ok").green().to_string()
+                } else {
+                    console::style("✗ failed").red().to_string()
+                },
+                record.identity.clone().unwrap_or_else(|| "—".to_string()),
+                format!("{} ms", record.elapsed_ms),
+            ],
+            SavedCheck::Missing {
+                step_id, method, ..
+            } => vec![
+                format!("{} ({})", step_id, method),
+                console::style("– missing").yellow().to_string(),
+                "—".to_string(),
+                "—".to_string(),
+            ],
+            SavedCheck::Recent {
+                step_id,
+                method,
+                validated_at,
+            } => vec![
+                format!("{} ({})", step_id, method),
+                console::style("✓ ok").green().to_string(),
+                "—".to_string(),
+                console::style(format!("validated {}", age(validated_at)))
+                    .dim()
+                    .to_string(),
+            ],
+        })
+        .collect();
+    ui::print_table(&["Validator", "Result", "Identity", "Latency"], &rows);
+    println!();
+
+    for check in &checks {
+        match check {
+            SavedCheck::Ran(record) => {
+                if let Some(ref error) = record.error {
+                    ui::print_warning(&format!("{}: {}", record.step_id, error));
+                }
+            }
+            SavedCheck::Missing {
+                step_id, missing, ..
+            } => ui::print_warning(&format!(
+                "{}: {} not set in {}",
+                step_id,
+                missing.join(", "),
+                output_file
+            )),
+            SavedCheck::Recent { .. } => {}
+        }
+    }
+
+    let failed: Vec<&ValidationRecord> = checks
+        .iter()
+        .filter_map(|c| match c {
+            SavedCheck::Ran(record) if !record.passed => Some(record),
+            _ => None,
+        })
+        .collect();
+    let missing: Vec<String> = checks
+        .iter()
+        .filter_map(|c| match c {
+            SavedCheck::Missing { missing, .. } => Some(missing.join(", ")),
+            _ => None,
+        })
+        .collect();
+    if !failed.is_empty() {
+        let summary = format!("{} of {} check(s) failed.", failed.len(), checks.len());
+        Err(if failed.iter().all(|r| r.network_error) {
+            GetapiError::Network(summary)
+        } else {
+            GetapiError::ValidationFailed(summary)
+        })
+    } else if !missing.is_empty() {
+        Err(GetapiError::MissingCredentials(format!(
+            "{} in {}",
+            missing.join(", "),
+            output_file
+        )))
+    } else {
+        ui::print_success(&format!("All {} check(s) passed.", checks.len()));
+        Ok(())
+    }
+}
+
+/// Validate several providers at once: every discovered provider, or just `provider` when
+/// only the JSON output was asked for.
+fn cmd_validate_all(
+    registry: &RecipeRegistry,
+    provider: Option<&str>,
+    json: bool,
+    max_age: Option<chrono::Duration>,
+) -> Result<()> {
+    let targets = match provider {
+        Some(provider) => vec![runner::batch::Target {
+            provider: provider.to_string(),
+            output_file: session::store::load(provider)
+                .map(|s| s.output_file)
+                .unwrap_or_else(|_| ".env".to_string()),
+        }],
+        None => runner::batch::discover()?,
+    };
+
+    if targets.is_empty() && !json {
+        ui::print_info("No providers to validate. Set one up with `getapi <provider>` first.");
+        return Ok(());
+    }
+
+    let state = session::state::load();
+    let results = runner::batch::run(
+        registry,
+        &targets,
+        runner::batch::MAX_WORKERS,
+        &state,
+        max_age,
+    )?;
+    for result in &results {
+        if let Some(recipe) = registry.find(&result.provider) {
+            let values = env_output::read_existing(&result.output_file);
+            record_validations(recipe, &result.checks, &values);
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        println!();
+        let rows: Vec<Vec<String>> = results
+            .iter()
+            .map(|result| {
+                let label = result.status.label();
+                let status = match result.status {
+                    Status::Ok => console::style(label).green(),
+                    Status::Failed => console::style(label).red(),
+                    Status::Missing => console::style(label).yellow(),
+                    Status::NoValidator => console::style(label).dim(),
+                    Status::NotFound => console::style(label).yellow(),
+                };
+                let identities: Vec<&str> = result
+                    .checks
+                    .iter()
+                    .filter_map(|c| match c {
+                        SavedCheck::Ran(record) => record.identity.as_deref(),
+                        _ => None,
+                    })
+                    .collect();
+                vec![
+                    result.provider.clone(),
+                    status.to_string(),
+                    if identities.is_empty() {
+                        "—".to_string()
+                    } else {
+                        identities.join(", ")
+                    },
+                    details(result),
+                ]
+            })
+            .collect();
+        ui::print_table(&["Provider", "Result", "Identity", "Details"], &rows);
+        println!();
+    }
+
+    let failed: Vec<&runner::batch::ProviderResult> = results
+        .iter()
+        .filter(|r| r.status == Status::Failed)
+        .collect();
+    let missing: Vec<&str> = results
+        .iter()
+        .filter(|r| r.status == Status::Missing)
+        .map(|r| r.provider.as_str())
+        .collect();
+    let not_found: Vec<&str> = results
+        .iter()
+        .filter(|r| r.status == Status::NotFound)
+        .map(|r| r.provider.as_str())
+        .collect();
+    if !failed.is_empty() {
+        let summary = format!("{} of {} provider(s) failed.", failed.len(), results.len());
+        // Only call it a network problem if no provider actually rejected its credentials
+        let unreachable = failed.iter().all(|r| {
+            r.error.is_none()
+                && r.checks.iter().all(|c| match c {
+                    SavedCheck::Ran(record) => record.passed || record.network_error,
+                    _ => true,
+                })
+        });
+        Err(if unreachable {
+            GetapiError::Network(summary)
+        } else {
+            GetapiError::ValidationFailed(summary)
+        })
+    } else if !not_found.is_empty() {
+        Err(GetapiError::ProviderNotFound(not_found.join(", ")))
+    } else if !missing.is_empty() {
+        Err(GetapiError::MissingCredentials(missing.join(", ")))
+    } else {
+        if !json {
+            ui::print_success(&format!("{} provider(s) checked.", results.len()));
+        }
+        Ok(())
+    }
+}
+
+/// The first thing worth knowing about a provider's result, for the summary table.
+fn details(result: &runner::batch::ProviderResult) -> String {
+    if let Some(ref error) = result.error {
+        return error.clone();
+    }
+    for check in &result.checks {
+        match check {
+            SavedCheck::Ran(record) if !record.passed => {
+                return format!(
+                    "{}: {}",
+                    record.step_id,
+                    record.error.as_deref().unwrap_or_default()
+                );
+            }
+            SavedCheck::Missing { missing, .. } => {
+                return format!("{} not set in {}", missing.join(", "), result.output_file);
+            }
+            _ => {}
+        }
+    }
+    let recent = result.checks.iter().filter_map(|c| match c {
+        SavedCheck::Recent { validated_at, .. } => Some(*validated_at),
+        _ => None,
+    });
+    if let Some(validated_at) = recent.min() {
+        return console::style(format!("validated {}, not rechecked", age(&validated_at)))
+            .dim()
+            .to_string();
+    }
+    console::style(&result.output_file).dim().to_string()
+}
+
+/// Remember when each key was last validated and whether it passed. Failing to save this is
+/// only worth a warning.
+fn record_validations(recipe: &Recipe, checks: &[SavedCheck], values: &HashMap<String, String>) {
+    let records: Vec<ValidationRecord> = checks
+        .iter()
+        .filter_map(|c| match c {
+            SavedCheck::Ran(record) => Some(record.clone()),
+            _ => None,
+        })
+        .collect();
+    if records.is_empty() {
+        return;
+    }
+    let mut state = session::state::load();
+    state.record(recipe, &records, values, chrono::Utc::now());
+    if let Err(e) = session::state::save(&state) {
+        ui::print_warning(&format!("Couldn't save validation results: {}", e));
+    }
+}
+
+/// "3 days ago", for a time in the past.
+fn age(at: &chrono::DateTime<chrono::Utc>) -> String {
+    let secs = (chrono::Utc::now() - *at).num_seconds().max(0);
+    ui::format_age(secs as u64)
+}
+
+fn cmd_list(
+    registry: &RecipeRegistry,
+    search: Option<String>,
+    category: Option<String>,
+) -> Result<()> {
+    let recipes = if let Some(ref query) = search {
+        registry.search(query)
+    } else if let Some(ref cat) = category {
+        registry.filter_by_category(cat)
+    } else {
+        registry.all().iter().collect()
+    };
+
+    if recipes.is_empty() {
+        ui::print_info("No providers found.");
+        return Ok(());
+    }
+
+    println!();
+    println!("  {}", console::style("Available providers").bold());
+    println!();
+
+    for recipe in recipes {
+        print!(
+            "  {}  {}",
+            console::style(&recipe.id).cyan().bold(),
+            recipe.display_name
+        );
+        println!("  {}", console::style(&recipe.description).dim());
+    }
+
+    Ok(())
+}
+
+fn cmd_status(registry: &RecipeRegistry) -> Result<()> {
+    let sessions = session::store::list_all()?;
+
+    println!();
+    println!("  {}", console::style("Provider status").bold());
+    println!();
+
+    if sessions.is_empty() {
+        ui::print_info("No sessions found. Run `getapi <provider>` to get started.");
+        return Ok(());
+    }
+
+    let state = session::state::load();
+    for session in &sessions {
+        let recipe = registry.find(&session.provider);
+
+        // Only count steps on the path the session's choices lead down
+        let route = recipe
+            .map(|r| runner::path::reachable(r, 0, &session.choices_made))
+            .unwrap_or_default();
+        let remaining: Vec<usize> = recipe
+            .map(|r| {
+                route
+                    .iter()
+                    .copied()
+                    .filter(|&i| {
+                        let id = r.steps[i].id().to_string();
+                        !session.completed_steps.contains(&id)
+                            && !session.skipped_steps.contains(&id)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let total_steps = route.len();
+        let completed = total_steps - remaining.len();
+
+        let mut status_str = match session.status {
+            SessionStatus::InProgress => console::style("in progress").yellow().to_string(),
+            SessionStatus::Paused => console::style("paused").yellow().to_string(),
+            SessionStatus::Completed => console::style("done ✓").green().to_string(),
+        };
+        // The last validation, from a run or `getapi validate`, says more than the session flag
+        let values = env_output::read_existing(&session.output_file);
+        let freshness = state
+            .providers
+            .get(&session.provider)
+            .and_then(|entries| session::state::freshness(entries, &values));
+        match freshness {
+            Some(Freshness::Validated(at)) => status_str.push_str(
+                &console::style(format!(" · validated {}", age(&at)))
+                    .dim()
+                    .to_string(),
+            ),
+            Some(Freshness::Failed(at)) => status_str.push_str(
+                &console::style(format!(" · failed validation {}", age(&at)))
+                    .yellow()
+                    .to_string(),
+            ),
+            Some(Freshness::Changed) => status_str.push_str(
+                &console::style(" · changed since last validation")
+                    .yellow()
+                    .to_string(),
+            ),
+            None if !session.unvalidated_steps.is_empty() => {
+                status_str.push_str(&console::style(" · not validated").yellow().to_string())
+            }
+            None => {}
+        }
+        if session.status != SessionStatus::Completed {
+            if let Some(secs) = recipe.and_then(|r| runner::path::estimated_seconds(r, &remaining))
+            {
+                let left = format!(" · {} left", ui::format_duration(secs));
+                status_str.push_str(&console::style(left).dim().to_string());
+            }
+        }
+
+        let display_name = recipe
+            .map(|r| r.display_name.as_str())
+            .unwrap_or(&session.provider);
+
+        let (display_completed, display_total) = if session.status == SessionStatus::Completed {
+            (completed, completed)
+        } else {
+            (completed, total_steps)
+        };
+
+        let width = 12;
+        let filled = (display_completed * width)
+            .checked_div(display_total)
+            .unwrap_or(0);
+        let empty = width - filled;
+        let bar = format!("{}{}", "█".repeat(filled), "░".repeat(empty));
+
+        println!(
+            "  {:<20} {}  {}/{}  {}",
+            display_name, bar, display_completed, display_total, status_str
+        );
+
+        for step in &session.skipped_steps {
+            println!(
+                "  {:<20} {}",
+                "",
+                console::style(format!(
+                    "skipped {} · getapi resume {} --step {}",
+                    step, session.provider, step
+                ))
+                .dim()
+            );
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+fn cmd_manifest(registry: &RecipeRegistry, _cli: &Cli) -> Result<()> {
+    match manifest::loader::load()? {
+        Some(manifest) => {
+            println!();
+            println!("  {}", console::style("Project API setup").bold());
+            println!();
+
+            for mp in &manifest.providers {
+                let recipe = registry.find(&mp.id);
+                let display = recipe
+                    .map(|r| r.display_name.clone())
+                    .unwrap_or_else(|| mp.id.clone());
+
+                let required_str = if mp.required { "" } else { " (optional)" };
+
+                println!(
+                    "  {} {}{}",
+                    console::style("▸").cyan(),
+                    display,
+                    console::style(required_str).dim()
+                );
+
+                if let Some(ref reason) = mp.reason {
+                    println!("    {}", console::style(reason).dim());
+                }
+            }
+
+            println!();
+            let required: Vec<_> = manifest
+                .providers
+                .iter()
+                .filter(|p| p.required)
+                .map(|p| p.id.clone())
+                .collect();
+
+            if required.is_empty() {
+                ui::print_info("No required providers. All are optional.");
+            } else {
+                ui::print_info(&format!("Run: getapi {}", required.join(" ")));
+            }
+
+            Ok(())
+        }
+        None => {
+            println!();
+            ui::print_info("No providers specified and no manifest found.");
+            ui::print_info("Usage: getapi <provider> [providers...]");
+            ui::print_info("Run `getapi list` to see available providers.");
+            ui::print_info("Run `getapi --help` for full usage information.");
+            Ok(())
+        }
+    }
+}
+
+fn cmd_update() -> Result<()> {
+    ui::print_info("Fetching latest recipes from the remote repository...");
+    match recipe::remote::fetch_and_cache_all() {
+        Ok(recipes) => {
+            ui::print_success(&format!(
+                "Updated {} recipes. Run `getapi list` to see available providers.",
+                recipes.len()
+            ));
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("\n  {} {}", console::style("Warning:").yellow().bold(), e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+fn cmd_test_recipe(registry: &RecipeRegistry, recipe: &str, cases: &[String]) -> Result<()> {
+    let recipe = if std::path::Path::new(recipe).is_file() {
+        recipe::loader::load_from_file(recipe)?
+    } else {
+        registry
+            .find(recipe)
+            .ok_or_else(|| GetapiError::ProviderNotFound(recipe.to_string()))?
+            .clone()
+    };
+
+    harness::run_cases(&recipe, cases)
+}
+
+fn cmd_reset(provider: Option<String>) -> Result<()> {
+    match provider {
+        Some(p) => {
+            session::store::delete(&p)?;
+            session::pending::clear(&p)?;
+            session::state::clear(&p)?;
+            ui::print_success(&format!("Session for '{}' has been reset.", p));
+        }
+        None => {
+            session::store::delete_all()?;
+            session::pending::clear_all()?;
+            session::state::clear_all()?;
+            ui::print_success("All sessions have been reset.");
+        }
+    }
+    Ok(())
+}
+
+/// The scripted frontend when running from an answers file, the full-screen one for `--tui` on
+/// a TTY, and line-mode terminal prompts otherwise. `done` lists steps finished in earlier runs.
+fn frontend(cli: &Cli, recipe: &Recipe, done: &[String]) -> Result<Box<dyn Frontend>> {
+    if let Some(ref path) = cli.answers {
+        return Ok(Box::new(ScriptedFrontend::echoing(Answers::load(path)?)));
+    }
+    let tty = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    // Credentials printed to stdout would land on the full-screen UI
+    let mut tui_error = None;
+    if cli.tui && tty && cli.output != Format::Stdout {
+        match TuiFrontend::new(recipe, done) {
+            Ok(tui) => return Ok(Box::new(tui)),
+            Err(e) => tui_error = Some(e),
+        }
+    }
+    if !cli.non_interactive {
+        // Ctrl-C at a prompt pauses the run instead of killing it
+        interrupt::install()?;
+    }
+    let mut frontend = TerminalFrontend::default();
+    if let Some(e) = tui_error {
+        frontend.warning(&format!(
+            "Couldn't start the full-screen UI ({}). Carrying on in the terminal.",
+            e
+        ));
+    }
+    Ok(Box::new(frontend))
+}
+
+/// A run context with the options given on the command line.
+fn context(cli: &Cli, output_file: &str, frontend: Box<dyn Frontend>) -> RunContext {
+    RunContext::builder(frontend)
+        .output_format(cli.output.into())
+        .output_file(output_file)
+        .non_interactive(cli.non_interactive)
+        .use_env(use_env(cli))
+        .skip(cli.skip.clone())
+        .strict(cli.strict)
+        .stage_credentials(stage_credentials(cli))
+        .build()
+}
+
+/// `--use-env` on the command line, or `use_env` in the project manifest.
+fn use_env(cli: &Cli) -> bool {
+    cli.use_env
+        || manifest::loader::load()
+            .ok()
+            .flatten()
+            .is_some_and(|m| m.use_env)
+}
+
+/// `--stage-credentials` on the command line, or `stage_credentials` in the project manifest.
+fn stage_credentials(cli: &Cli) -> bool {
+    cli.stage_credentials
+        || manifest::loader::load()
+            .ok()
+            .flatten()
+            .is_some_and(|m| m.stage_credentials)
+}
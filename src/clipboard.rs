@@ -0,0 +1,120 @@
+//! Thin wrapper around the system clipboard. Every operation degrades to a no-op when no
+//! clipboard is available (e.g. headless Linux or SSH sessions).
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::secrets;
+
+/// Delay before a sensitive value copied by getapi is cleared, unless the step overrides it.
+pub const DEFAULT_CLEAR_AFTER_SECS: u64 = 30;
+
+/// Environment variable the clearing helper gets the fingerprint from. Unlike its arguments, a
+/// process's environment isn't visible to other users.
+pub const FINGERPRINT_VAR: &str = "GETAPI_CLEAR_FINGERPRINT";
+
+pub fn read() -> Option<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut cb| cb.get_text())
+        .ok()
+        .filter(|text| !text.trim().is_empty())
+}
+
+pub fn write(value: &str) -> bool {
+    arboard::Clipboard::new()
+        .and_then(|mut cb| cb.set_text(value))
+        .is_ok()
+}
+
+/// Clear the clipboard, but only if it still holds `value`.
+pub fn clear_if_matches(value: &str) -> bool {
+    match arboard::Clipboard::new() {
+        Ok(mut cb) => match cb.get_text() {
+            Ok(current) if current == value => cb.clear().is_ok(),
+            _ => false,
+        },
+        Err(_) => false,
+    }
+}
+
+/// Spawn a detached helper that clears the clipboard after `after_secs`, if it still holds
+/// `value` by then. The helper outlives this process and only receives a fingerprint of the
+/// value, never the value itself.
+pub fn schedule_clear(value: &str, after_secs: u64) -> bool {
+    let Ok(exe) = std::env::current_exe() else {
+        return false;
+    };
+    clear_command(&exe, value, after_secs).spawn().is_ok()
+}
+
+fn clear_command(exe: &Path, value: &str, after_secs: u64) -> Command {
+    let mut cmd = Command::new(exe);
+    cmd.arg("__clear-clipboard")
+        .arg("--after")
+        .arg(after_secs.to_string())
+        .env(FINGERPRINT_VAR, secrets::fingerprint(value))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    // Detach from the terminal's process group so Ctrl-C doesn't take the helper down too.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const DETACHED_PROCESS: u32 = 0x0000_0008;
+        cmd.creation_flags(DETACHED_PROCESS);
+    }
+    cmd
+}
+
+/// Entry point for the detached helper started by `schedule_clear`.
+pub fn clear_after(after_secs: u64, fingerprint: &str) {
+    std::thread::sleep(Duration::from_secs(after_secs));
+
+    if let Ok(mut cb) = arboard::Clipboard::new() {
+        if still_holds(cb.get_text().ok().as_deref(), fingerprint) {
+            let _ = cb.clear();
+        }
+    }
+}
+
+/// Whether the clipboard's `current` text is the value `fingerprint` was taken from.
+fn still_holds(current: Option<&str>, fingerprint: &str) -> bool {
+    current.is_some_and(|text| secrets::fingerprint(text) == fingerprint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_command_keeps_value_off_argv() {
+        let cmd = clear_command(Path::new("getapi"), "sk-secret", 30);
+        let args: Vec<&std::ffi::OsStr> = cmd.get_args().collect();
+        assert_eq!(args, ["__clear-clipboard", "--after", "30"]);
+
+        let fingerprint = secrets::fingerprint("sk-secret");
+        let env: Vec<_> = cmd.get_envs().collect();
+        assert_eq!(
+            env,
+            [(
+                std::ffi::OsStr::new(FINGERPRINT_VAR),
+                Some(std::ffi::OsStr::new(&fingerprint))
+            )]
+        );
+    }
+
+    #[test]
+    fn test_still_holds() {
+        let fingerprint = secrets::fingerprint("sk-secret");
+        assert!(still_holds(Some("sk-secret"), &fingerprint));
+        assert!(!still_holds(Some("something else"), &fingerprint));
+        assert!(!still_holds(None, &fingerprint));
+    }
+}
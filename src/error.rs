@@ -14,9 +14,15 @@ pub enum GetapiError {
     #[error("Step '{0}' not found in recipe")]
     StepNotFound(String),
 
+    #[error("Step '{0}' can't be skipped. Only steps marked `skippable` in the recipe can be.")]
+    NotSkippable(String),
+
     #[error("Template variable '{0}' not set. This step depends on a previous choice or input.")]
     TemplateVarNotFound(String),
 
+    #[error("Missing credentials: {0}")]
+    MissingCredentials(String),
+
     #[error("Validation failed: {0}")]
     ValidationFailed(String),
 
@@ -26,9 +32,37 @@ pub enum GetapiError {
     #[error("Session error: {0}")]
     SessionError(String),
 
+    #[error("{0}")]
+    AnswersError(String),
+
+    #[error("No answer for step '{0}' in the answers file.")]
+    MissingAnswer(String),
+
+    #[error("Invalid answer for step '{0}': {1}")]
+    InvalidAnswer(String, String),
+
+    #[error("{0} recipe test case(s) failed.")]
+    RecipeTestFailed(usize),
+
+    #[error("Could not start the web server: {0}")]
+    WebServer(String),
+
+    /// A recorded response for `--replay-http` is missing or unreadable. This is a problem with
+    /// the fixtures, not the credentials, so it stops the run instead of failing validation.
+    #[error("Cassette error: {0}")]
+    Cassette(String),
+
+    /// Raised by a frontend when the user asks to go back a step; handled by the runner.
+    #[error("Went back to the previous step.")]
+    GoBack,
+
     #[error("User cancelled the operation.")]
     UserCancelled,
 
+    /// The user stopped to do something outside getapi first; the session is saved as paused.
+    #[error("Setup paused.")]
+    Paused,
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -38,8 +72,42 @@ pub enum GetapiError {
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
+    #[error("Network error: {0}")]
+    Network(String),
+
     #[error("Failed to fetch remote recipes: {0}")]
     RemoteFetch(String),
 }
 
+impl GetapiError {
+    /// The process exit code for this error, so scripts can tell failures apart:
+    ///
+    /// | Code | Meaning |
+    /// |------|---------|
+    /// | 1 | Any other error |
+    /// | 3 | Credentials are missing |
+    /// | 4 | Credentials failed validation |
+    /// | 5 | Provider not found |
+    /// | 6 | Recipe is invalid |
+    /// | 7 | Network error |
+    /// | 75 | Paused, to be resumed later |
+    /// | 130 | Cancelled |
+    ///
+    /// Code 2 is left to clap for usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            GetapiError::MissingCredentials(_) => 3,
+            GetapiError::ValidationFailed(_) => 4,
+            GetapiError::ProviderNotFound(_) => 5,
+            GetapiError::InvalidRecipe(_)
+            | GetapiError::RecipeFileNotFound(_)
+            | GetapiError::ValidatorNotFound(_, _) => 6,
+            GetapiError::Http(_) | GetapiError::Network(_) | GetapiError::RemoteFetch(_) => 7,
+            GetapiError::Paused => 75,
+            GetapiError::UserCancelled => 130,
+            _ => 1,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, GetapiError>;
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{GetapiError, Result};
+
+/// Pre-recorded answers that let a recipe run end to end without a TTY.
+///
+/// ```json
+/// {
+///   "answers": {
+///     "check_access": "The developer dashboard",
+///     "confirm_project": true,
+///     "collect_api_key": "env:TWITTER_API_KEY",
+///     "collect_api_secret": "file:secrets/twitter_secret.txt",
+///     "apply_access": "skip"
+///   },
+///   "skip_waits": false
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[non_exhaustive]
+pub struct Answers {
+    /// Step id → answer
+    #[serde(default)]
+    pub answers: HashMap<String, Answer>,
+    /// Skip every `wait` step instead of requiring a `"skip"` answer for each
+    #[serde(default)]
+    pub skip_waits: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Bool(bool),
+    Text(String),
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Self> {
+        let p = Path::new(path);
+        if !p.exists() {
+            return Err(GetapiError::AnswersError(format!(
+                "Answers file not found: {}",
+                path
+            )));
+        }
+        let contents = std::fs::read_to_string(p)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| GetapiError::AnswersError(format!("Invalid answers file: {}", e)))
+    }
+
+    fn get(&self, step_id: &str) -> Result<&Answer> {
+        self.answers
+            .get(step_id)
+            .ok_or_else(|| GetapiError::MissingAnswer(step_id.to_string()))
+    }
+
+    /// Answer for a `prompt_confirm` step. Accepts `true`/`false` or `"yes"`/`"no"`.
+    pub fn confirm(&self, step_id: &str) -> Result<bool> {
+        match self.get(step_id)? {
+            Answer::Bool(b) => Ok(*b),
+            Answer::Text(t) => match t.to_lowercase().as_str() {
+                "yes" | "y" | "true" => Ok(true),
+                "no" | "n" | "false" => Ok(false),
+                _ => Err(invalid(step_id, format!("expected yes or no, got '{}'", t))),
+            },
+        }
+    }
+
+    /// Answer for a `prompt_choice` step, matched against the choice labels.
+    pub fn choice(&self, step_id: &str, labels: &[&str]) -> Result<usize> {
+        let label = match self.get(step_id)? {
+            Answer::Text(t) => t,
+            Answer::Bool(_) => {
+                return Err(invalid(step_id, "expected a choice label".to_string()));
+            }
+        };
+        labels.iter().position(|l| l == label).ok_or_else(|| {
+            invalid(
+                step_id,
+                format!(
+                    "'{}' is not one of the choices: {}",
+                    label,
+                    labels.join(", ")
+                ),
+            )
+        })
+    }
+
+    /// Answer for a `prompt_input` step, resolving `env:VAR` and `file:path` references.
+    pub fn input(&self, step_id: &str) -> Result<String> {
+        let text = match self.get(step_id)? {
+            Answer::Text(t) => t,
+            Answer::Bool(_) => return Err(invalid(step_id, "expected a string".to_string())),
+        };
+
+        if let Some(var) = text.strip_prefix("env:") {
+            return std::env::var(var)
+                .map_err(|_| invalid(step_id, format!("environment variable {} is not set", var)));
+        }
+
+        if let Some(path) = text.strip_prefix("file:") {
+            return std::fs::read_to_string(path)
+                .map(|s| s.trim().to_string())
+                .map_err(|e| invalid(step_id, format!("could not read {}: {}", path, e)));
+        }
+
+        Ok(text.clone())
+    }
+
+    /// Whether a `wait` step has been declared skippable.
+    pub fn skips_wait(&self, step_id: &str) -> bool {
+        self.skip_waits || matches!(self.answers.get(step_id), Some(Answer::Text(t)) if t == "skip")
+    }
+}
+
+fn invalid(step_id: &str, reason: String) -> GetapiError {
+    GetapiError::InvalidAnswer(step_id.to_string(), reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Answers {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_choice_and_confirm() {
+        let answers = parse(r#"{ "answers": { "pick": "Live keys", "ok": "yes", "no": false } }"#);
+        assert_eq!(
+            answers.choice("pick", &["Test keys", "Live keys"]).unwrap(),
+            1
+        );
+        assert!(answers.choice("pick", &["Other"]).is_err());
+        assert!(answers.confirm("ok").unwrap());
+        assert!(!answers.confirm("no").unwrap());
+    }
+
+    #[test]
+    fn test_missing_answer_names_step() {
+        let answers = parse("{}");
+        let err = answers.input("collect_key").unwrap_err();
+        assert!(err.to_string().contains("collect_key"));
+    }
+
+    #[test]
+    fn test_file_reference() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.txt");
+        std::fs::write(&path, "sk-123\n").unwrap();
+        let answers = parse(&format!(
+            r#"{{ "answers": {{ "key": "file:{}" }} }}"#,
+            path.display()
+        ));
+        assert_eq!(answers.input("key").unwrap(), "sk-123");
+    }
+
+    #[test]
+    fn test_skip_waits() {
+        let answers = parse(r#"{ "answers": { "wait_a": "skip" } }"#);
+        assert!(answers.skips_wait("wait_a"));
+        assert!(!answers.skips_wait("wait_b"));
+        assert!(parse(r#"{ "skip_waits": true }"#).skips_wait("wait_b"));
+    }
+}
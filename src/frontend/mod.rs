@@ -0,0 +1,160 @@
+pub mod answers;
+pub mod rpc;
+pub mod scripted;
+pub mod terminal;
+pub mod tui;
+
+use std::process::{Command, ExitStatus, Stdio};
+
+use crate::error::Result;
+use crate::recipe::types::FailureAction;
+use crate::ui;
+use crate::validators::ValidationRecord;
+
+/// A line of output from the runner, rendered by the active frontend.
+#[derive(Debug, Clone, Copy)]
+pub enum Message<'a> {
+    Header {
+        name: &'a str,
+        description: &'a str,
+    },
+    Meta {
+        label: &'a str,
+        value: &'a str,
+    },
+    StepCounter {
+        step_id: &'a str,
+        current: usize,
+        total: usize,
+        /// Estimated seconds left, including this step
+        remaining: Option<u64>,
+    },
+    /// The current step was skipped at the user's request
+    StepSkipped {
+        step_id: &'a str,
+    },
+    Info(&'a str),
+    /// Something for the user to do themselves, in a `--non-interactive` checklist
+    Action(&'a str),
+    Url(&'a str),
+    Command(&'a str),
+    Success(&'a str),
+    Warning(&'a str),
+    Section(&'a str),
+    Bullet(&'a str),
+    Pause(&'a str),
+    Blank,
+}
+
+/// Everything the runner needs from the user: output, prompts, the browser and the clipboard.
+///
+/// Prompts receive the id of the step asking, so non-interactive frontends can answer them
+/// from a script.
+pub trait Frontend {
+    fn print(&mut self, message: Message);
+
+    fn confirm(&mut self, step_id: &str, prompt: &str, default: bool) -> Result<bool>;
+
+    fn select(
+        &mut self,
+        step_id: &str,
+        prompt: &str,
+        items: &[&str],
+        default: usize,
+    ) -> Result<usize>;
+
+    fn input(&mut self, step_id: &str, prompt: &str) -> Result<String>;
+
+    /// Like `input`, but the value isn't echoed back.
+    fn secret_input(&mut self, step_id: &str, prompt: &str) -> Result<String>;
+
+    /// Called when a value from `input` fails validation. Interactive frontends show the reason
+    /// and ask again; scripted ones fail, since the same answer would be given again.
+    fn reject_input(&mut self, step_id: &str, reason: &str) -> Result<()>;
+
+    /// Offer to open `url` in the user's browser.
+    fn open_url(&mut self, step_id: &str, url: &str) -> Result<()>;
+
+    /// Whether a `wait` step should pause the session (`true`) or carry on (`false`).
+    fn should_pause(&mut self, step_id: &str) -> Result<bool>;
+
+    /// Called before each step with whether there's an earlier step to go back to. Frontends
+    /// that offer "back" return `GetapiError::GoBack` from a prompt to rewind.
+    fn set_can_go_back(&mut self, _can_go_back: bool) {}
+
+    /// Ask whether to skip a `skippable` step before it runs.
+    fn offer_skip(&mut self, step_id: &str) -> Result<bool> {
+        let prompt = format!("Skip \"{}\"?", ui::step_title(step_id));
+        let items = ["No, do this step", "Yes, I've already done this"];
+        Ok(self.select(step_id, &prompt, &items, 0)? == 1)
+    }
+
+    /// Ask what to do after a validate step fails. `can_reenter` says whether there are inputs
+    /// to go back to; `can_save` is false under `--strict`.
+    fn validation_failed(
+        &mut self,
+        step_id: &str,
+        can_reenter: bool,
+        can_save: bool,
+    ) -> Result<FailureAction> {
+        let mut actions = Vec::new();
+        if can_reenter {
+            actions.push((FailureAction::Reenter, "Re-enter the credentials"));
+        }
+        actions.push((FailureAction::Retry, "Try again"));
+        if can_save {
+            actions.push((FailureAction::SaveAnyway, "Save them anyway"));
+        }
+        actions.push((FailureAction::Abort, "Stop without writing them"));
+        let labels: Vec<&str> = actions.iter().map(|(_, label)| *label).collect();
+        let selection = self.select(step_id, "What would you like to do?", &labels, 0)?;
+        Ok(actions[selection].0)
+    }
+
+    /// Called with the outcome of each validate step, after it has been reported.
+    fn validation_result(&mut self, _record: &ValidationRecord) {}
+
+    /// Run a recipe's shell command. By default its output is collected and shown as messages,
+    /// so it can't land in the middle of a full-screen UI or a protocol stream.
+    fn run_command(&mut self, _step_id: &str, command: &str) -> Result<ExitStatus> {
+        let output = shell(command).stdin(Stdio::null()).output()?;
+        for stream in [&output.stdout, &output.stderr] {
+            for line in String::from_utf8_lossy(stream).lines() {
+                self.info(line);
+            }
+        }
+        Ok(output.status)
+    }
+
+    /// A credential was collected, whether typed, pasted, read from the environment or picked
+    /// up from an earlier run. Frontends that send output elsewhere keep it out of what they
+    /// send.
+    fn add_secret(&mut self, _value: &str) {}
+
+    fn read_clipboard(&mut self) -> Option<String>;
+
+    /// Put `value` on the clipboard, clearing it again after `clear_after` seconds if set.
+    fn write_clipboard(&mut self, value: &str, clear_after: Option<u64>) -> bool;
+
+    /// Clear the clipboard, but only if it still holds `value`.
+    fn clear_clipboard(&mut self, value: &str) -> bool;
+
+    fn info(&mut self, message: &str) {
+        self.print(Message::Info(message));
+    }
+
+    fn success(&mut self, message: &str) {
+        self.print(Message::Success(message));
+    }
+
+    fn warning(&mut self, message: &str) {
+        self.print(Message::Warning(message));
+    }
+}
+
+/// `command` run through the shell, as recipes write it.
+pub fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
@@ -0,0 +1,200 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde_json::{json, Value};
+
+use crate::clipboard;
+use crate::error::{GetapiError, Result};
+use crate::frontend::{Frontend, Message};
+use crate::rpc::connection::Connection;
+use crate::validators::ValidationRecord;
+
+/// A frontend for `getapi serve --stdio`: output becomes JSON-RPC notifications and prompts
+/// become requests the client answers.
+pub struct RpcFrontend {
+    conn: Rc<RefCell<Connection>>,
+    can_go_back: bool,
+}
+
+impl RpcFrontend {
+    pub fn new(conn: Rc<RefCell<Connection>>) -> Self {
+        Self {
+            conn,
+            can_go_back: false,
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        // A client that went away surfaces as a cancelled prompt; nothing to do here
+        let _ = self.conn.borrow_mut().notify(method, params);
+    }
+
+    /// Send a prompt. Every prompt says whether the client may answer `{"back": true}` to go
+    /// back a step.
+    fn request(&mut self, method: &str, mut params: Value) -> Result<Value> {
+        params["can_go_back"] = json!(self.can_go_back);
+        let answer = self.conn.borrow_mut().request(method, params)?;
+        if self.can_go_back && answer.get("back").and_then(Value::as_bool) == Some(true) {
+            return Err(GetapiError::GoBack);
+        }
+        Ok(answer)
+    }
+}
+
+fn invalid(step_id: &str, answer: &Value) -> GetapiError {
+    GetapiError::InvalidAnswer(step_id.to_string(), format!("unexpected answer {}", answer))
+}
+
+impl Frontend for RpcFrontend {
+    fn print(&mut self, message: Message) {
+        let (method, params) = match message {
+            Message::StepSkipped { step_id } => ("step_skipped", json!({ "step_id": step_id })),
+            Message::StepCounter {
+                step_id,
+                current,
+                total,
+                remaining,
+            } => (
+                "step_started",
+                json!({
+                    "step_id": step_id,
+                    "current": current,
+                    "total": total,
+                    "remaining_seconds": remaining,
+                }),
+            ),
+            Message::Header { name, description } => (
+                "message",
+                json!({ "kind": "header", "title": name, "text": description }),
+            ),
+            Message::Meta { label, value } => (
+                "message",
+                json!({ "kind": "meta", "label": label, "text": value }),
+            ),
+            Message::Info(text) => ("message", json!({ "kind": "info", "text": text })),
+            Message::Action(text) => ("message", json!({ "kind": "action", "text": text })),
+            Message::Url(text) => ("message", json!({ "kind": "url", "text": text })),
+            Message::Command(text) => ("message", json!({ "kind": "command", "text": text })),
+            Message::Success(text) => ("message", json!({ "kind": "success", "text": text })),
+            Message::Warning(text) => ("message", json!({ "kind": "warning", "text": text })),
+            Message::Section(text) => ("message", json!({ "kind": "section", "text": text })),
+            Message::Bullet(text) => ("message", json!({ "kind": "bullet", "text": text })),
+            Message::Pause(text) => ("message", json!({ "kind": "pause", "text": text })),
+            Message::Blank => return,
+        };
+        self.notify(method, params);
+    }
+
+    fn confirm(&mut self, step_id: &str, prompt: &str, default: bool) -> Result<bool> {
+        let answer = self.request(
+            "prompt_confirm",
+            json!({ "step_id": step_id, "prompt": prompt, "default": default }),
+        )?;
+        answer.as_bool().ok_or_else(|| invalid(step_id, &answer))
+    }
+
+    fn select(
+        &mut self,
+        step_id: &str,
+        prompt: &str,
+        items: &[&str],
+        default: usize,
+    ) -> Result<usize> {
+        let answer = self.request(
+            "prompt_choice",
+            json!({ "step_id": step_id, "prompt": prompt, "choices": items, "default": default }),
+        )?;
+        answer
+            .as_u64()
+            .map(|i| i as usize)
+            .filter(|&i| i < items.len())
+            .ok_or_else(|| invalid(step_id, &answer))
+    }
+
+    fn input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+        let answer = self.request(
+            "prompt_input",
+            json!({ "step_id": step_id, "prompt": prompt, "secret": false }),
+        )?;
+        answer
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| invalid(step_id, &answer))
+    }
+
+    fn secret_input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+        let answer = self.request(
+            "prompt_input",
+            json!({ "step_id": step_id, "prompt": prompt, "secret": true }),
+        )?;
+        let value = answer
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| invalid(step_id, &answer))?;
+
+        self.add_secret(&value);
+        Ok(value)
+    }
+
+    fn reject_input(&mut self, step_id: &str, reason: &str) -> Result<()> {
+        self.notify(
+            "input_rejected",
+            json!({ "step_id": step_id, "reason": reason }),
+        );
+        Ok(())
+    }
+
+    fn open_url(&mut self, step_id: &str, url: &str) -> Result<()> {
+        self.notify("open_url", json!({ "step_id": step_id, "url": url }));
+        Ok(())
+    }
+
+    fn should_pause(&mut self, step_id: &str) -> Result<bool> {
+        let answer = self.request("prompt_wait", json!({ "step_id": step_id }))?;
+        answer.as_bool().ok_or_else(|| invalid(step_id, &answer))
+    }
+
+    fn validation_result(&mut self, record: &ValidationRecord) {
+        self.notify(
+            "validation_result",
+            json!({
+                "step_id": record.step_id,
+                "method": record.method,
+                "passed": record.passed,
+                "skipped": record.skipped,
+                "error": record.error,
+                "identity": record.identity,
+                "elapsed_ms": record.elapsed_ms,
+            }),
+        );
+    }
+
+    fn set_can_go_back(&mut self, can_go_back: bool) {
+        self.can_go_back = can_go_back;
+    }
+
+    // The server runs on the user's machine, so the system clipboard is shared with the client
+    fn read_clipboard(&mut self) -> Option<String> {
+        clipboard::read()
+    }
+
+    fn add_secret(&mut self, value: &str) {
+        let mut conn = self.conn.borrow_mut();
+        conn.add_secret(value);
+        conn.add_secret(value.trim());
+    }
+
+    fn write_clipboard(&mut self, value: &str, clear_after: Option<u64>) -> bool {
+        if !clipboard::write(value) {
+            return false;
+        }
+        if let Some(secs) = clear_after {
+            clipboard::schedule_clear(value, secs);
+        }
+        true
+    }
+
+    fn clear_clipboard(&mut self, value: &str) -> bool {
+        clipboard::clear_if_matches(value)
+    }
+}
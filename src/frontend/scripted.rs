@@ -0,0 +1,128 @@
+use crate::error::{GetapiError, Result};
+use crate::frontend::answers::Answers;
+use crate::frontend::{terminal, Frontend, Message};
+use crate::recipe::types::FailureAction;
+
+/// A frontend that answers every prompt from pre-recorded `Answers`, so recipes can run
+/// without a TTY (`--answers`) and be exercised deterministically in tests.
+pub struct ScriptedFrontend {
+    answers: Answers,
+    /// Echo output and answers to the terminal
+    echo: bool,
+    clipboard: Option<String>,
+}
+
+impl ScriptedFrontend {
+    /// A silent frontend, for tests.
+    pub fn new(answers: Answers) -> Self {
+        Self {
+            answers,
+            echo: false,
+            clipboard: None,
+        }
+    }
+
+    /// A frontend that still prints the walkthrough, for `--answers` runs.
+    pub fn echoing(answers: Answers) -> Self {
+        Self {
+            echo: true,
+            ..Self::new(answers)
+        }
+    }
+
+    fn echo_answer(&mut self, prompt: &str, answer: &str) {
+        self.print(Message::Info(prompt));
+        self.print(Message::Success(answer));
+    }
+}
+
+impl Frontend for ScriptedFrontend {
+    fn print(&mut self, message: Message) {
+        if self.echo {
+            terminal::print(message);
+        }
+    }
+
+    fn confirm(&mut self, step_id: &str, prompt: &str, _default: bool) -> Result<bool> {
+        let confirmed = self.answers.confirm(step_id)?;
+        self.echo_answer(prompt, if confirmed { "Yes" } else { "No" });
+        Ok(confirmed)
+    }
+
+    fn select(
+        &mut self,
+        step_id: &str,
+        prompt: &str,
+        items: &[&str],
+        _default: usize,
+    ) -> Result<usize> {
+        let selection = self.answers.choice(step_id, items)?;
+        self.echo_answer(prompt, items[selection]);
+        Ok(selection)
+    }
+
+    fn input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+        let value = self.answers.input(step_id)?;
+        self.echo_answer(prompt, "Provided by the answers file.");
+        Ok(value)
+    }
+
+    fn secret_input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+        self.input(step_id, prompt)
+    }
+
+    fn reject_input(&mut self, step_id: &str, reason: &str) -> Result<()> {
+        Err(GetapiError::InvalidAnswer(
+            step_id.to_string(),
+            reason.to_string(),
+        ))
+    }
+
+    fn open_url(&mut self, _step_id: &str, _url: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn should_pause(&mut self, step_id: &str) -> Result<bool> {
+        if !self.answers.skips_wait(step_id) {
+            return Err(GetapiError::MissingAnswer(step_id.to_string()));
+        }
+        self.print(Message::Info("Skipped (declared in the answers file)."));
+        Ok(false)
+    }
+
+    // Retrying would give the same answer, so scripted runs carry on as before unless --strict
+    fn validation_failed(
+        &mut self,
+        _step_id: &str,
+        _can_reenter: bool,
+        can_save: bool,
+    ) -> Result<FailureAction> {
+        if can_save {
+            Ok(FailureAction::SaveAnyway)
+        } else {
+            Ok(FailureAction::Abort)
+        }
+    }
+
+    // Scripted runs skip steps with `--skip`
+    fn offer_skip(&mut self, _step_id: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn read_clipboard(&mut self) -> Option<String> {
+        self.clipboard.clone()
+    }
+
+    fn write_clipboard(&mut self, value: &str, _clear_after: Option<u64>) -> bool {
+        self.clipboard = Some(value.to_string());
+        true
+    }
+
+    fn clear_clipboard(&mut self, value: &str) -> bool {
+        if self.clipboard.as_deref() == Some(value) {
+            self.clipboard = None;
+            return true;
+        }
+        false
+    }
+}
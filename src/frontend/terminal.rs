@@ -0,0 +1,216 @@
+use std::process::ExitStatus;
+
+use console::{Key, Term};
+use dialoguer::{Confirm, Input, Password, Select};
+
+use crate::clipboard;
+use crate::error::{GetapiError, Result};
+use crate::frontend::{self, Frontend, Message};
+use crate::{interrupt, ui};
+
+/// Typed at a text prompt to go back a step.
+const BACK_INPUT: &str = "<";
+const BACK_ITEM: &str = "← Back";
+
+/// The interactive terminal frontend, built on dialoguer and console.
+#[derive(Default)]
+pub struct TerminalFrontend {
+    can_go_back: bool,
+    /// Whether the user has been told how to go back
+    hinted: bool,
+}
+
+/// Fails if Ctrl-C was pressed, including while a prompt that can't see it was reading a line.
+fn check_interrupt() -> Result<()> {
+    if interrupt::requested() {
+        return Err(GetapiError::UserCancelled);
+    }
+    Ok(())
+}
+
+/// Read a value without echoing it. Unlike dialoguer's `Password`, this reads key by key in raw
+/// mode, so Ctrl-C cancels the prompt instead of waiting for Enter.
+fn read_secret(prompt: &str) -> std::io::Result<String> {
+    let term = Term::stderr();
+    if !term.is_term() {
+        return Password::new()
+            .with_prompt(prompt)
+            .allow_empty_password(true)
+            .interact()
+            .map_err(|dialoguer::Error::IO(e)| e);
+    }
+
+    term.write_str(&format!("{}: ", prompt))?;
+    let mut value = String::new();
+    loop {
+        match term.read_key()? {
+            Key::Enter => break,
+            Key::Backspace => {
+                value.pop();
+            }
+            Key::Char(c) if !c.is_control() => value.push(c),
+            _ => {}
+        }
+    }
+    term.clear_line()?;
+    term.write_line(&format!("{}: {}", prompt, console::style("[hidden]").dim()))?;
+    Ok(value)
+}
+
+impl TerminalFrontend {
+    fn back_or(&self, value: String) -> Result<String> {
+        check_interrupt()?;
+        if self.can_go_back && value.trim() == BACK_INPUT {
+            return Err(GetapiError::GoBack);
+        }
+        Ok(value)
+    }
+}
+
+impl Frontend for TerminalFrontend {
+    fn print(&mut self, message: Message) {
+        print(message);
+    }
+
+    fn confirm(&mut self, _step_id: &str, prompt: &str, default: bool) -> Result<bool> {
+        check_interrupt()?;
+        let answer = Confirm::new()
+            .with_prompt(prompt)
+            .default(default)
+            .interact_opt()
+            .map_err(|_| GetapiError::UserCancelled)?;
+        match answer {
+            Some(confirmed) => Ok(confirmed),
+            // Esc
+            None if self.can_go_back => Err(GetapiError::GoBack),
+            None => Err(GetapiError::UserCancelled),
+        }
+    }
+
+    fn select(
+        &mut self,
+        _step_id: &str,
+        prompt: &str,
+        items: &[&str],
+        default: usize,
+    ) -> Result<usize> {
+        check_interrupt()?;
+        let mut items = items.to_vec();
+        if self.can_go_back {
+            items.push(BACK_ITEM);
+        }
+
+        let selection = Select::new()
+            .with_prompt(prompt)
+            .items(&items)
+            .default(default)
+            .interact_opt()
+            .map_err(|_| GetapiError::UserCancelled)?;
+        match selection {
+            Some(i) if self.can_go_back && i == items.len() - 1 => Err(GetapiError::GoBack),
+            Some(i) => Ok(i),
+            None if self.can_go_back => Err(GetapiError::GoBack),
+            None => Err(GetapiError::UserCancelled),
+        }
+    }
+
+    fn input(&mut self, _step_id: &str, prompt: &str) -> Result<String> {
+        check_interrupt()?;
+        let value = Input::new()
+            .with_prompt(prompt)
+            .interact_text()
+            .map_err(|_| GetapiError::UserCancelled)?;
+        self.back_or(value)
+    }
+
+    fn secret_input(&mut self, _step_id: &str, prompt: &str) -> Result<String> {
+        check_interrupt()?;
+        let value = read_secret(prompt).map_err(|_| GetapiError::UserCancelled)?;
+        self.back_or(value)
+    }
+
+    fn set_can_go_back(&mut self, can_go_back: bool) {
+        self.can_go_back = can_go_back;
+        if can_go_back && !self.hinted {
+            self.hinted = true;
+            println!(
+                "  {}",
+                console::style(format!(
+                    "Made a mistake? Choose \"{}\", press Esc, or enter {} to go back a step.",
+                    BACK_ITEM, BACK_INPUT
+                ))
+                .dim()
+            );
+        }
+    }
+
+    fn reject_input(&mut self, _step_id: &str, reason: &str) -> Result<()> {
+        ui::print_warning(reason);
+        Ok(())
+    }
+
+    fn open_url(&mut self, step_id: &str, url: &str) -> Result<()> {
+        if self.confirm(step_id, "Open in browser?", true)? {
+            if let Err(e) = open::that(url) {
+                ui::print_warning(&format!(
+                    "Could not open browser automatically: {}. Open the URL above manually.",
+                    e
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn should_pause(&mut self, _step_id: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    /// Commands run in the foreground, so their output and any prompts they show come through
+    /// as they happen.
+    fn run_command(&mut self, _step_id: &str, command: &str) -> Result<ExitStatus> {
+        Ok(frontend::shell(command).status()?)
+    }
+
+    fn read_clipboard(&mut self) -> Option<String> {
+        clipboard::read()
+    }
+
+    fn write_clipboard(&mut self, value: &str, clear_after: Option<u64>) -> bool {
+        if !clipboard::write(value) {
+            return false;
+        }
+        if let Some(secs) = clear_after {
+            clipboard::schedule_clear(value, secs);
+        }
+        true
+    }
+
+    fn clear_clipboard(&mut self, value: &str) -> bool {
+        clipboard::clear_if_matches(value)
+    }
+}
+
+/// Render a runner message with the `ui` helpers.
+pub fn print(message: Message) {
+    match message {
+        Message::Header { name, description } => ui::print_header(name, description),
+        Message::Meta { label, value } => ui::print_meta(label, value),
+        Message::StepCounter {
+            current,
+            total,
+            remaining,
+            ..
+        } => ui::print_step_counter(current, total, remaining),
+        Message::StepSkipped { .. } => ui::print_info("Skipped."),
+        Message::Info(m) => ui::print_info(m),
+        Message::Action(m) => ui::print_action(m),
+        Message::Url(m) => ui::print_url(m),
+        Message::Command(m) => ui::print_command(m),
+        Message::Success(m) => ui::print_success(m),
+        Message::Warning(m) => ui::print_warning(m),
+        Message::Section(m) => ui::print_section(m),
+        Message::Bullet(m) => ui::print_bullet(m),
+        Message::Pause(m) => ui::print_pause(m),
+        Message::Blank => println!(),
+    }
+}
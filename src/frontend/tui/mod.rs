@@ -0,0 +1,396 @@
+mod state;
+
+use ratatui::crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::clipboard;
+use crate::error::{GetapiError, Result};
+use crate::frontend::{terminal, Frontend, Message};
+use crate::recipe::types::Recipe;
+use crate::ui;
+
+use state::{Entry, KeyAction, Kind, Prompt, PromptInput, Screen, StepState};
+
+/// A full-screen frontend: a sidebar of steps, the current step's messages, inline prompts and
+/// the recipe's gotchas. Only used on a TTY; see `TerminalFrontend` for line mode.
+pub struct TuiFrontend {
+    terminal: DefaultTerminal,
+    screen: Screen,
+}
+
+impl TuiFrontend {
+    /// Take over the terminal for `recipe`. `done` lists steps already completed in an earlier
+    /// run, so a resumed session shows them as done. Fails, leaving the terminal as it was, if
+    /// it can't be switched to full-screen mode.
+    pub fn new(recipe: &Recipe, done: &[String]) -> Result<Self> {
+        let setup = ratatui::try_init().and_then(|terminal| {
+            execute!(std::io::stdout(), EnableBracketedPaste)?;
+            Ok(terminal)
+        });
+        let terminal = match setup {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                // Setup can fail halfway, with raw mode already on
+                ratatui::restore();
+                return Err(e.into());
+            }
+        };
+
+        let screen = Screen::new(recipe, done);
+        Ok(Self { terminal, screen })
+    }
+
+    fn redraw(&mut self, prompt: Option<&Prompt>) {
+        let view = View {
+            screen: &self.screen,
+            prompt,
+        };
+        // Drawing only fails if the terminal went away, which the next key read reports
+        let _ = self.terminal.draw(|frame| view.render(frame));
+    }
+
+    /// Show `prompt` and handle keys until it's answered. Esc goes back a step when there is one
+    /// to go back to, and cancels otherwise; Ctrl-C always cancels.
+    fn ask(&mut self, mut prompt: Prompt) -> Result<PromptInput> {
+        loop {
+            self.redraw(Some(&prompt));
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                Event::Paste(text) => {
+                    prompt.input.paste(&text);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            match prompt.input.handle_key(key, self.screen.can_go_back) {
+                KeyAction::Edit => {}
+                KeyAction::Submit => return Ok(prompt.input),
+                KeyAction::Back => return Err(GetapiError::GoBack),
+                KeyAction::Cancel => return Err(GetapiError::UserCancelled),
+            }
+        }
+    }
+
+    fn text_input(&mut self, prompt: &str, secret: bool) -> Result<String> {
+        let answer = self.ask(Prompt {
+            label: prompt.to_string(),
+            input: PromptInput::Text {
+                value: String::new(),
+                secret,
+            },
+        })?;
+        let PromptInput::Text { value, .. } = answer else {
+            unreachable!("text prompts are answered with text");
+        };
+
+        let shown = if secret { "********" } else { value.as_str() };
+        self.screen
+            .push(Kind::Info, &format!("{} {}", prompt, shown));
+        Ok(value)
+    }
+}
+
+impl Drop for TuiFrontend {
+    fn drop(&mut self) {
+        let _ = execute!(std::io::stdout(), DisableBracketedPaste);
+        ratatui::restore();
+
+        // Leave the last step's messages (the summary and gotchas) in the scrollback
+        for entry in &self.screen.entries {
+            terminal::print(entry.message());
+        }
+    }
+}
+
+impl Entry {
+    fn line(&self) -> Line<'_> {
+        let text = self.text.as_str();
+        match self.kind {
+            Kind::Info | Kind::Blank => Line::from(text),
+            Kind::Action => Line::from(vec!["→ ".cyan(), text.into()]),
+            Kind::Url => Line::from(vec!["→ ".cyan(), text.underlined()]),
+            Kind::Command => Line::from(vec!["$ ".dark_gray(), text.bold()]),
+            Kind::Success => Line::from(vec!["✓ ".green().bold(), text.into()]),
+            Kind::Warning => Line::from(vec!["! ".yellow().bold(), text.into()]),
+            Kind::Section => Line::from(text.bold().underlined()),
+            Kind::Bullet => Line::from(vec!["• ".dark_gray(), text.into()]),
+            Kind::Pause => Line::from(vec!["⏸ ".cyan().bold(), text.into()]),
+        }
+    }
+}
+
+struct View<'a> {
+    screen: &'a Screen,
+    prompt: Option<&'a Prompt>,
+}
+
+impl View<'_> {
+    fn render(&self, frame: &mut Frame) {
+        let f = self.screen;
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(2 + u16::from(!f.meta.is_empty())),
+            Constraint::Min(5),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let [sidebar, main] =
+            Layout::horizontal([Constraint::Length(30), Constraint::Min(20)]).areas(body);
+
+        let prompt_height = match self.prompt.map(|p| &p.input) {
+            Some(PromptInput::Select { items, .. }) => items.len() as u16 + 3,
+            Some(_) => 4,
+            None => 0,
+        };
+        let gotchas_height = if f.gotchas.is_empty() {
+            0
+        } else {
+            (f.gotchas.len() as u16 + 2).min(8)
+        };
+        let [messages, prompt, gotchas] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(prompt_height),
+            Constraint::Length(gotchas_height),
+        ])
+        .areas(main);
+
+        self.render_header(frame, header);
+        self.render_steps(frame, sidebar);
+        self.render_messages(frame, messages);
+        if let Some(p) = self.prompt {
+            render_prompt(frame, prompt, p);
+        }
+        if gotchas_height > 0 {
+            let lines: Vec<Line> = f
+                .gotchas
+                .iter()
+                .map(|g| Line::from(vec!["• ".dark_gray(), g.as_str().into()]))
+                .collect();
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .wrap(Wrap { trim: false })
+                    .block(Block::bordered().title(" Gotchas ")),
+                gotchas,
+            );
+        }
+
+        frame.render_widget(
+            Paragraph::new(f.help(self.prompt.map(|p| &p.input)).dark_gray()),
+            footer,
+        );
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let f = self.screen;
+        let mut title = vec![
+            "▸ ".cyan().bold(),
+            Span::styled(f.name.as_str(), Style::new().add_modifier(Modifier::BOLD)),
+        ];
+        if let Some((current, total, remaining)) = f.counter {
+            title.push(format!("   [{}/{}]", current, total).dark_gray());
+            if let Some(secs) = remaining {
+                title.push(format!(" · {} left", ui::format_duration(secs)).dark_gray());
+            }
+        }
+
+        let mut lines = vec![
+            Line::from(title),
+            Line::from(f.description.as_str().dark_gray()),
+        ];
+        if !f.meta.is_empty() {
+            lines.push(Line::from(f.meta.join(" · ").dark_gray()));
+        }
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn render_steps(&self, frame: &mut Frame, area: Rect) {
+        let f = self.screen;
+        let items: Vec<ListItem> = f
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, (id, title))| {
+                let line = match f.state(i, id) {
+                    StepState::Done => Line::from(vec!["✓ ".green(), title.as_str().into()]),
+                    StepState::Current => Line::from(format!("▸ {}", title).cyan().bold()),
+                    StepState::Skipped => Line::from(
+                        format!("– {}", title)
+                            .dark_gray()
+                            .add_modifier(Modifier::CROSSED_OUT),
+                    ),
+                    StepState::Pending => Line::from(format!("  {}", title)),
+                };
+                ListItem::new(line)
+            })
+            .collect();
+        frame.render_widget(
+            List::new(items).block(Block::bordered().title(" Steps ")),
+            area,
+        );
+    }
+
+    fn render_messages(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = self.screen.entries.iter().map(Entry::line).collect();
+        // Keep the newest messages in view
+        let visible = area.height.saturating_sub(2) as usize;
+        let scroll = lines.len().saturating_sub(visible) as u16;
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((scroll, 0))
+                .block(Block::bordered()),
+            area,
+        );
+    }
+}
+
+fn render_prompt(frame: &mut Frame, area: Rect, prompt: &Prompt) {
+    let block = Block::bordered()
+        .title(format!(" {} ", prompt.label))
+        .border_style(Style::new().fg(Color::Cyan));
+
+    let lines: Vec<Line> = match &prompt.input {
+        PromptInput::Text { value, secret } => {
+            let shown = if *secret {
+                "•".repeat(value.chars().count())
+            } else {
+                value.clone()
+            };
+            vec![Line::from(vec![Span::raw(shown), "█".cyan()])]
+        }
+        PromptInput::Select { items, selected } => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                if i == *selected {
+                    Line::from(format!("▸ {}", item).cyan().bold())
+                } else {
+                    Line::from(format!("  {}", item))
+                }
+            })
+            .collect(),
+        PromptInput::Confirm { yes } => {
+            let option = |label: &'static str, chosen: bool| {
+                if chosen {
+                    format!("[{}]", label).cyan().bold()
+                } else {
+                    format!(" {} ", label).into()
+                }
+            };
+            vec![Line::from(vec![
+                option("Yes", *yes),
+                "  ".into(),
+                option("No", !*yes),
+            ])]
+        }
+    };
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+impl Frontend for TuiFrontend {
+    fn print(&mut self, message: Message) {
+        self.screen.apply(message);
+        self.redraw(None);
+    }
+
+    fn confirm(&mut self, _step_id: &str, prompt: &str, default: bool) -> Result<bool> {
+        let answer = self.ask(Prompt {
+            label: prompt.to_string(),
+            input: PromptInput::Confirm { yes: default },
+        })?;
+        let PromptInput::Confirm { yes } = answer else {
+            unreachable!("confirm prompts are answered with yes or no");
+        };
+        self.screen.push(
+            Kind::Info,
+            &format!("{} {}", prompt, if yes { "Yes" } else { "No" }),
+        );
+        Ok(yes)
+    }
+
+    fn select(
+        &mut self,
+        _step_id: &str,
+        prompt: &str,
+        items: &[&str],
+        default: usize,
+    ) -> Result<usize> {
+        let answer = self.ask(Prompt {
+            label: prompt.to_string(),
+            input: PromptInput::Select {
+                items: items.iter().map(|s| s.to_string()).collect(),
+                selected: default,
+            },
+        })?;
+        let PromptInput::Select { selected, .. } = answer else {
+            unreachable!("select prompts are answered with a selection");
+        };
+        self.screen
+            .push(Kind::Info, &format!("{} {}", prompt, items[selected]));
+        Ok(selected)
+    }
+
+    fn input(&mut self, _step_id: &str, prompt: &str) -> Result<String> {
+        self.text_input(prompt, false)
+    }
+
+    fn secret_input(&mut self, _step_id: &str, prompt: &str) -> Result<String> {
+        self.text_input(prompt, true)
+    }
+
+    fn reject_input(&mut self, _step_id: &str, reason: &str) -> Result<()> {
+        self.screen.push(Kind::Warning, reason);
+        Ok(())
+    }
+
+    fn open_url(&mut self, step_id: &str, url: &str) -> Result<()> {
+        if self.confirm(step_id, "Open in browser?", true)? {
+            if let Err(e) = open::that(url) {
+                self.screen.push(
+                    Kind::Warning,
+                    &format!(
+                        "Could not open browser automatically: {}. Open the URL above manually.",
+                        e
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn should_pause(&mut self, _step_id: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn read_clipboard(&mut self) -> Option<String> {
+        clipboard::read()
+    }
+
+    fn write_clipboard(&mut self, value: &str, clear_after: Option<u64>) -> bool {
+        if !clipboard::write(value) {
+            return false;
+        }
+        if let Some(secs) = clear_after {
+            clipboard::schedule_clear(value, secs);
+        }
+        true
+    }
+
+    fn clear_clipboard(&mut self, value: &str) -> bool {
+        clipboard::clear_if_matches(value)
+    }
+
+    fn set_can_go_back(&mut self, can_go_back: bool) {
+        self.screen.can_go_back = can_go_back;
+    }
+}
@@ -0,0 +1,413 @@
+//! What the full-screen frontend shows, and how keys change it, kept apart from the terminal so
+//! it can be tested without one.
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::frontend::Message;
+use crate::recipe::types::Recipe;
+use crate::ui;
+
+/// Everything on screen apart from the active prompt.
+pub(super) struct Screen {
+    pub name: String,
+    pub description: String,
+    pub meta: Vec<String>,
+    /// Step ids and their sidebar titles, in recipe order
+    pub steps: Vec<(String, String)>,
+    pub gotchas: Vec<String>,
+    /// Steps finished before this run started (on resume)
+    pub done: Vec<String>,
+    pub visited: Vec<String>,
+    /// Steps skipped at the user's request
+    pub skipped: Vec<String>,
+    pub counter: Option<(usize, usize, Option<u64>)>,
+    pub can_go_back: bool,
+    /// Messages shown for the current step
+    pub entries: Vec<Entry>,
+}
+
+pub(super) struct Entry {
+    pub kind: Kind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Kind {
+    Info,
+    Action,
+    Url,
+    Command,
+    Success,
+    Warning,
+    Section,
+    Bullet,
+    Pause,
+    Blank,
+}
+
+pub(super) struct Prompt {
+    pub label: String,
+    pub input: PromptInput,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) enum PromptInput {
+    Text { value: String, secret: bool },
+    Select { items: Vec<String>, selected: usize },
+    Confirm { yes: bool },
+}
+
+/// How a step is shown in the sidebar.
+#[derive(Debug, PartialEq)]
+pub(super) enum StepState {
+    Done,
+    Current,
+    Skipped,
+    Pending,
+}
+
+/// What a key press does to the prompt being answered.
+#[derive(Debug, PartialEq)]
+pub(super) enum KeyAction {
+    /// The answer was edited, or the key did nothing
+    Edit,
+    /// The prompt is answered
+    Submit,
+    /// Go back to the previous step
+    Back,
+    Cancel,
+}
+
+impl Screen {
+    /// A screen for `recipe`, with `done` steps already ticked off.
+    pub fn new(recipe: &Recipe, done: &[String]) -> Self {
+        Self {
+            name: recipe.display_name.clone(),
+            description: recipe.description.clone(),
+            meta: Vec::new(),
+            steps: recipe
+                .steps
+                .iter()
+                .map(|s| (s.id().to_string(), ui::step_title(s.id())))
+                .collect(),
+            gotchas: recipe.gotchas.clone(),
+            done: done.to_vec(),
+            visited: Vec::new(),
+            skipped: Vec::new(),
+            counter: None,
+            can_go_back: false,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, kind: Kind, text: &str) {
+        self.entries.push(Entry {
+            kind,
+            text: text.to_string(),
+        });
+    }
+
+    /// Update the screen for a message from the runner. A new step clears the previous step's
+    /// messages, and one that was skipped earlier and is now being run is no longer skipped.
+    pub fn apply(&mut self, message: Message) {
+        match message {
+            Message::Header { name, description } => {
+                self.name = name.to_string();
+                self.description = description.to_string();
+            }
+            Message::Meta { label, value } => self.meta.push(format!("{}: {}", label, value)),
+            Message::StepCounter {
+                step_id,
+                current,
+                total,
+                remaining,
+            } => {
+                self.counter = Some((current, total, remaining));
+                self.visited.push(step_id.to_string());
+                self.skipped.retain(|s| s != step_id);
+                self.entries.clear();
+            }
+            Message::StepSkipped { step_id } => self.skipped.push(step_id.to_string()),
+            Message::Info(m) => self.push(Kind::Info, m),
+            Message::Action(m) => self.push(Kind::Action, m),
+            Message::Url(m) => self.push(Kind::Url, m),
+            Message::Command(m) => self.push(Kind::Command, m),
+            Message::Success(m) => self.push(Kind::Success, m),
+            Message::Warning(m) => self.push(Kind::Warning, m),
+            Message::Section(m) => self.push(Kind::Section, m),
+            Message::Bullet(m) => self.push(Kind::Bullet, m),
+            Message::Pause(m) => self.push(Kind::Pause, m),
+            Message::Blank => self.push(Kind::Blank, ""),
+        }
+    }
+
+    /// How step `id`, at `index` in the recipe, appears in the sidebar. Steps the path jumped
+    /// over are shown as skipped, like the ones the user skipped.
+    pub fn state(&self, index: usize, id: &str) -> StepState {
+        let current = self.visited.last().map(String::as_str);
+        if self.skipped.iter().any(|s| s == id) {
+            return StepState::Skipped;
+        }
+        if current == Some(id) {
+            return StepState::Current;
+        }
+        if self.visited.iter().any(|v| v == id) || self.done.iter().any(|d| d == id) {
+            return StepState::Done;
+        }
+        let furthest = self
+            .steps
+            .iter()
+            .rposition(|(step, _)| self.visited.contains(step));
+        match furthest {
+            Some(furthest) if index < furthest => StepState::Skipped,
+            _ => StepState::Pending,
+        }
+    }
+
+    /// The key hints shown under `prompt`.
+    pub fn help(&self, prompt: Option<&PromptInput>) -> String {
+        let keys = match prompt {
+            Some(PromptInput::Select { .. }) => "↑/↓ choose · Enter select",
+            Some(PromptInput::Confirm { .. }) => "y/n answer · Enter accept",
+            Some(PromptInput::Text { .. }) => "Type or paste · Enter submit",
+            None => "Working…",
+        };
+        let escape = match (prompt.is_some(), self.can_go_back) {
+            (false, _) => "",
+            (true, true) => " · Esc back · Ctrl-C cancel",
+            (true, false) => " · Esc cancel",
+        };
+        format!("{}{}", keys, escape)
+    }
+}
+
+impl Entry {
+    /// The message this entry was made from, for printing once the screen is gone.
+    pub fn message(&self) -> Message<'_> {
+        let text = self.text.as_str();
+        match self.kind {
+            Kind::Info => Message::Info(text),
+            Kind::Action => Message::Action(text),
+            Kind::Url => Message::Url(text),
+            Kind::Command => Message::Command(text),
+            Kind::Success => Message::Success(text),
+            Kind::Warning => Message::Warning(text),
+            Kind::Section => Message::Section(text),
+            Kind::Bullet => Message::Bullet(text),
+            Kind::Pause => Message::Pause(text),
+            Kind::Blank => Message::Blank,
+        }
+    }
+}
+
+impl PromptInput {
+    /// Apply a key press. Esc goes back a step when `can_go_back`, and cancels otherwise;
+    /// Ctrl-C always cancels. Answering y or n submits a confirm straight away.
+    pub fn handle_key(&mut self, key: KeyEvent, can_go_back: bool) -> KeyAction {
+        let ctrl_c =
+            key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+        if key.code == KeyCode::Esc && can_go_back {
+            return KeyAction::Back;
+        }
+        if ctrl_c || key.code == KeyCode::Esc {
+            return KeyAction::Cancel;
+        }
+
+        match (self, key.code) {
+            (_, KeyCode::Enter) => return KeyAction::Submit,
+            (PromptInput::Text { value, .. }, KeyCode::Char(c)) => value.push(c),
+            (PromptInput::Text { value, .. }, KeyCode::Backspace) => {
+                value.pop();
+            }
+            (PromptInput::Select { selected, .. }, KeyCode::Up | KeyCode::Char('k')) => {
+                *selected = selected.saturating_sub(1);
+            }
+            (PromptInput::Select { items, selected }, KeyCode::Down | KeyCode::Char('j')) => {
+                *selected = (*selected + 1).min(items.len().saturating_sub(1));
+            }
+            (PromptInput::Confirm { yes }, KeyCode::Char('y' | 'Y')) => {
+                *yes = true;
+                return KeyAction::Submit;
+            }
+            (PromptInput::Confirm { yes }, KeyCode::Char('n' | 'N')) => {
+                *yes = false;
+                return KeyAction::Submit;
+            }
+            (PromptInput::Confirm { yes }, KeyCode::Left | KeyCode::Right | KeyCode::Tab) => {
+                *yes = !*yes
+            }
+            _ => {}
+        }
+        KeyAction::Edit
+    }
+
+    /// Add pasted text to a text prompt, without the trailing newline a terminal paste often
+    /// carries. Other prompts ignore pastes.
+    pub fn paste(&mut self, text: &str) {
+        if let PromptInput::Text { value, .. } = self {
+            value.push_str(text.trim_end_matches(['\r', '\n']));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen() -> Screen {
+        let recipe = Recipe::from_json(
+            r#"{
+                "schema_version": "1",
+                "id": "demo",
+                "display_name": "Demo",
+                "description": "A demo recipe",
+                "category": ["testing"],
+                "steps": [
+                    { "id": "sign_up", "type": "info", "message": "" },
+                    { "id": "create_project", "type": "info", "message": "" },
+                    { "id": "create_key", "type": "info", "message": "" },
+                    { "id": "done", "type": "info", "message": "" }
+                ],
+                "outputs": []
+            }"#,
+        )
+        .unwrap();
+        Screen::new(&recipe, &["sign_up".to_string()])
+    }
+
+    fn enter_step(screen: &mut Screen, step_id: &str) {
+        screen.apply(Message::StepCounter {
+            step_id,
+            current: 1,
+            total: 4,
+            remaining: None,
+        });
+    }
+
+    fn states(screen: &Screen) -> Vec<StepState> {
+        screen
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, (id, _))| screen.state(i, id))
+            .collect()
+    }
+
+    fn press(input: &mut PromptInput, code: KeyCode, can_go_back: bool) -> KeyAction {
+        input.handle_key(KeyEvent::new(code, KeyModifiers::NONE), can_go_back)
+    }
+
+    #[test]
+    fn test_sidebar_states() {
+        let mut screen = screen();
+        assert_eq!(screen.steps[1].1, "Create project");
+        use StepState::*;
+        assert_eq!(states(&screen), vec![Done, Pending, Pending, Pending]);
+
+        // Skipping a step marks it as such until it's run again
+        enter_step(&mut screen, "create_project");
+        screen.apply(Message::StepSkipped {
+            step_id: "create_project",
+        });
+        enter_step(&mut screen, "create_key");
+        assert_eq!(states(&screen), vec![Done, Skipped, Current, Pending]);
+        enter_step(&mut screen, "create_project");
+        assert_eq!(states(&screen), vec![Done, Current, Done, Pending]);
+
+        // A step the path jumped over shows as skipped
+        let mut screen = self::screen();
+        enter_step(&mut screen, "sign_up");
+        enter_step(&mut screen, "done");
+        assert_eq!(states(&screen), vec![Done, Skipped, Skipped, Current]);
+    }
+
+    #[test]
+    fn test_new_step_clears_messages() {
+        let mut screen = screen();
+        screen.apply(Message::Info("first"));
+        screen.apply(Message::Blank);
+        assert_eq!(screen.entries.len(), 2);
+        assert_eq!(screen.entries[0].kind, Kind::Info);
+
+        enter_step(&mut screen, "create_key");
+        assert!(screen.entries.is_empty());
+        assert_eq!(screen.counter, Some((1, 4, None)));
+        screen.apply(Message::Warning("careful"));
+        assert!(matches!(
+            screen.entries[0].message(),
+            Message::Warning("careful")
+        ));
+    }
+
+    #[test]
+    fn test_escape_goes_back_or_cancels() {
+        let mut input = PromptInput::Confirm { yes: true };
+        assert_eq!(press(&mut input, KeyCode::Esc, true), KeyAction::Back);
+        assert_eq!(press(&mut input, KeyCode::Esc, false), KeyAction::Cancel);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(input.handle_key(ctrl_c, true), KeyAction::Cancel);
+
+        let mut screen = screen();
+        let prompt = Some(&input);
+        assert_eq!(
+            screen.help(prompt),
+            "y/n answer · Enter accept · Esc cancel"
+        );
+        screen.can_go_back = true;
+        assert_eq!(
+            screen.help(prompt),
+            "y/n answer · Enter accept · Esc back · Ctrl-C cancel"
+        );
+        assert_eq!(screen.help(None), "Working…");
+    }
+
+    #[test]
+    fn test_text_keys() {
+        let mut input = PromptInput::Text {
+            value: String::new(),
+            secret: false,
+        };
+        assert_eq!(
+            press(&mut input, KeyCode::Char('a'), false),
+            KeyAction::Edit
+        );
+        press(&mut input, KeyCode::Char('b'), false);
+        press(&mut input, KeyCode::Backspace, false);
+        input.paste("-key\r\n");
+        assert_eq!(press(&mut input, KeyCode::Enter, false), KeyAction::Submit);
+        assert_eq!(
+            input,
+            PromptInput::Text {
+                value: "a-key".to_string(),
+                secret: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_select_and_confirm_keys() {
+        let mut input = PromptInput::Select {
+            items: vec!["one".to_string(), "two".to_string()],
+            selected: 0,
+        };
+        press(&mut input, KeyCode::Up, false);
+        press(&mut input, KeyCode::Char('j'), false);
+        press(&mut input, KeyCode::Down, false);
+        input.paste("ignored");
+        assert!(matches!(input, PromptInput::Select { selected: 1, .. }));
+
+        let mut input = PromptInput::Confirm { yes: true };
+        press(&mut input, KeyCode::Tab, false);
+        assert_eq!(input, PromptInput::Confirm { yes: false });
+        assert_eq!(
+            press(&mut input, KeyCode::Char('Y'), false),
+            KeyAction::Submit
+        );
+        assert_eq!(input, PromptInput::Confirm { yes: true });
+        assert_eq!(
+            press(&mut input, KeyCode::Char('n'), false),
+            KeyAction::Submit
+        );
+        assert_eq!(input, PromptInput::Confirm { yes: false });
+    }
+}
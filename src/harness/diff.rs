@@ -0,0 +1,67 @@
+/// One line of a line-based diff between expected and actual values.
+#[derive(Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Expected(&'a str),
+    Actual(&'a str),
+}
+
+/// Line diff based on the longest common subsequence. Inputs here are step and key lists, so
+/// the quadratic table is fine.
+pub fn diff<'a>(expected: &'a [String], actual: &'a [String]) -> Vec<DiffLine<'a>> {
+    let (n, m) = (expected.len(), actual.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if expected[i] == actual[j] {
+            lines.push(DiffLine::Same(&expected[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Expected(&expected[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Actual(&actual[j]));
+            j += 1;
+        }
+    }
+    lines.extend(expected[i..].iter().map(|s| DiffLine::Expected(s)));
+    lines.extend(actual[j..].iter().map(|s| DiffLine::Actual(s)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let expected = strings(&["intro", "pick", "collect", "validate"]);
+        let actual = strings(&["intro", "pick", "wait", "collect"]);
+        assert_eq!(
+            diff(&expected, &actual),
+            vec![
+                DiffLine::Same("intro"),
+                DiffLine::Same("pick"),
+                DiffLine::Actual("wait"),
+                DiffLine::Same("collect"),
+                DiffLine::Expected("validate"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,212 @@
+pub mod diff;
+pub mod types;
+
+use std::path::Path;
+
+use console::style;
+
+use crate::error::{GetapiError, Result};
+use crate::frontend::scripted::ScriptedFrontend;
+use crate::harness::diff::DiffLine;
+use crate::harness::types::{TestCase, ValidationExpectation};
+use crate::http;
+use crate::output::env as env_output;
+use crate::recipe::deps;
+use crate::recipe::types::Recipe;
+use crate::runner;
+use crate::runner::context::RunContext;
+use crate::ui;
+
+/// A field whose actual value didn't match the case's expectation.
+pub struct Mismatch {
+    pub field: &'static str,
+    pub expected: Vec<String>,
+    pub actual: Vec<String>,
+}
+
+pub fn load_case(path: &str) -> Result<TestCase> {
+    let p = Path::new(path);
+    if !p.exists() {
+        return Err(GetapiError::RecipeFileNotFound(path.to_string()));
+    }
+    let contents = std::fs::read_to_string(p)?;
+    serde_json::from_str(&contents)
+        .map_err(|e| GetapiError::InvalidRecipe(format!("Invalid test case {}: {}", path, e)))
+}
+
+/// Run `recipe` headlessly with the case's answers and mock HTTP responses, and compare the
+/// outcome against its expectations.
+pub fn run_case(recipe: &Recipe, case: &TestCase) -> Vec<Mismatch> {
+    let output_file = std::env::temp_dir().join(format!(
+        "getapi-test-{}-{}.env",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let output_path = output_file.to_string_lossy().to_string();
+
+    let mut ctx = RunContext::builder(Box::new(ScriptedFrontend::new(case.answers.clone())))
+        .output_file(output_path.clone())
+        .build();
+
+    // Cases without inline responses use cassettes when recording or replaying
+    let mocked = !case.http.is_empty() || http::mode() == http::Mode::Live;
+    if mocked {
+        http::set_mocks(case.http.clone());
+    }
+    let result = runner::run(recipe, &mut ctx);
+    http::clear_mocks();
+
+    let written = env_output::read_existing(&output_path);
+    let _ = std::fs::remove_file(&output_file);
+
+    let expect = &case.expect;
+    let mut mismatches = Vec::new();
+    let mut check = |field, expected: Vec<String>, actual: Vec<String>| {
+        if expected != actual {
+            mismatches.push(Mismatch {
+                field,
+                expected,
+                actual,
+            });
+        }
+    };
+
+    let error = result.err().map(|e| e.to_string());
+    match (&expect.error, &error) {
+        (Some(wanted), Some(actual)) if actual.contains(wanted.as_str()) => {}
+        (None, None) => {}
+        (wanted, actual) => check(
+            "error",
+            wanted.iter().cloned().collect(),
+            actual.iter().cloned().collect(),
+        ),
+    }
+
+    if let Some(ref visited) = expect.visited {
+        check("visited", visited.clone(), ctx.visited.clone());
+    }
+
+    if let Some(ref collected) = expect.collected {
+        let mut expected = collected.clone();
+        expected.sort();
+        let mut actual: Vec<String> = ctx.collected.keys().cloned().collect();
+        actual.sort();
+        check("collected", expected, actual);
+    }
+
+    if let Some(validation) = expect.validation {
+        let actual = if ctx.validations.is_empty() {
+            ValidationExpectation::None
+        } else if ctx.validations.iter().any(|v| !v.passed && !v.skipped) {
+            ValidationExpectation::Failed
+        } else if ctx.validations.iter().any(|v| v.skipped) {
+            ValidationExpectation::Skipped
+        } else {
+            ValidationExpectation::Passed
+        };
+        let mut actual_lines = vec![actual.as_str().to_string()];
+        actual_lines.extend(
+            ctx.validations
+                .iter()
+                .filter_map(|v| v.error.as_ref().map(|e| format!("{}: {}", v.step_id, e))),
+        );
+        if validation != actual {
+            check(
+                "validation",
+                vec![validation.as_str().to_string()],
+                actual_lines,
+            );
+        }
+    }
+
+    if let Some(ref output) = expect.output {
+        let lines = |map: &std::collections::HashMap<String, String>| {
+            let mut lines: Vec<String> = map.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            lines.sort();
+            lines
+        };
+        check("output", lines(output), lines(&written));
+    }
+
+    if let Some(paused) = expect.paused {
+        check(
+            "paused",
+            vec![paused.to_string()],
+            vec![ctx.paused.to_string()],
+        );
+    }
+
+    mismatches
+}
+
+pub fn print_mismatch(mismatch: &Mismatch) {
+    println!("      {}", style(format!("{}:", mismatch.field)).bold());
+    if mismatch.expected.is_empty() && mismatch.actual.is_empty() {
+        return;
+    }
+    for line in diff::diff(&mismatch.expected, &mismatch.actual) {
+        match line {
+            DiffLine::Same(s) => println!("        {}", style(s).dim()),
+            DiffLine::Expected(s) => println!("      {} {}", style("-").red(), style(s).red()),
+            DiffLine::Actual(s) => println!("      {} {}", style("+").green(), style(s).green()),
+        }
+    }
+}
+
+/// Run every case file against `recipe`, printing a pass/fail line per case and a diff for
+/// each mismatch. Fails if any case fails.
+pub fn run_cases(recipe: &Recipe, case_paths: &[String]) -> Result<()> {
+    println!();
+    println!(
+        "  {}",
+        style(format!(
+            "Testing {} ({} case{})",
+            recipe.id,
+            case_paths.len(),
+            if case_paths.len() == 1 { "" } else { "s" }
+        ))
+        .bold()
+    );
+    println!();
+
+    // Broken `depends_on` entries fail the run, whatever the cases say
+    let problems = deps::problems(recipe);
+    for problem in &problems {
+        println!("  {} depends_on: {}", style("✗").red().bold(), problem);
+    }
+
+    let mut failed = 0;
+    for path in case_paths {
+        let case = load_case(path)?;
+        let name = case.name.clone().unwrap_or_else(|| path.clone());
+        let mismatches = run_case(recipe, &case);
+
+        if mismatches.is_empty() {
+            ui::print_success(&name);
+        } else {
+            failed += 1;
+            println!("  {} {}", style("✗").red().bold(), name);
+            println!("      {}", style("(- expected, + actual)").dim());
+            for mismatch in &mismatches {
+                print_mismatch(mismatch);
+            }
+        }
+    }
+
+    println!();
+    if !problems.is_empty() {
+        return Err(GetapiError::InvalidRecipe(format!(
+            "{} depends_on problem(s)",
+            problems.len()
+        )));
+    }
+
+    let passed = case_paths.len() - failed;
+    if failed == 0 {
+        ui::print_success(&format!("{} passed", passed));
+        Ok(())
+    } else {
+        ui::print_warning(&format!("{} passed, {} failed", passed, failed));
+        Err(GetapiError::RecipeTestFailed(failed))
+    }
+}
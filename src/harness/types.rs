@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::frontend::answers::Answers;
+use crate::http::MockResponse;
+
+/// A scripted run of a recipe and what it is expected to do.
+///
+/// ```json
+/// {
+///   "name": "dashboard path",
+///   "answers": { "check_access": "The developer dashboard", "collect_key": "sk-..." },
+///   "http": [{ "method": "GET", "url": "https://api.example.com/me", "status": 200 }],
+///   "expect": {
+///     "visited": ["intro", "check_access", "collect_key", "validate_key"],
+///     "collected": ["EXAMPLE_API_KEY"],
+///     "validation": "passed",
+///     "output": { "EXAMPLE_API_KEY": "sk-..." }
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct TestCase {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub answers: Answers,
+    /// Canned responses for validator requests. Requests without a match fail. When empty and
+    /// `--replay-http` or `--record-http` is active, cassettes are used instead.
+    #[serde(default)]
+    pub http: Vec<MockResponse>,
+    #[serde(default)]
+    pub expect: Expectations,
+}
+
+/// Every expectation is optional; only the ones given are checked, except `error`, which
+/// defaults to expecting a clean run.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Expectations {
+    /// Step ids executed, in order
+    #[serde(default)]
+    pub visited: Option<Vec<String>>,
+    /// Output keys collected by the end of the run
+    #[serde(default)]
+    pub collected: Option<Vec<String>>,
+    #[serde(default)]
+    pub validation: Option<ValidationExpectation>,
+    /// Values written to the output file
+    #[serde(default)]
+    pub output: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub paused: Option<bool>,
+    /// A substring of the error the run is expected to fail with
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationExpectation {
+    /// Every validate step that ran passed
+    Passed,
+    /// At least one validate step failed
+    Failed,
+    /// None failed, but at least one was skipped because its dependencies were missing
+    Skipped,
+    /// No validate step ran
+    None,
+}
+
+impl ValidationExpectation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationExpectation::Passed => "passed",
+            ValidationExpectation::Failed => "failed",
+            ValidationExpectation::Skipped => "skipped",
+            ValidationExpectation::None => "none",
+        }
+    }
+}
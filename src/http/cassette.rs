@@ -0,0 +1,241 @@
+//! Recorded request/response pairs ("cassettes") for replaying validator traffic offline.
+//!
+//! Cassettes are redacted before they are written: collected secrets are scrubbed from the URL,
+//! headers and bodies, the values of credential-bearing headers are replaced with `[REDACTED]`,
+//! and token-like fields in JSON responses are blanked. Requests are matched on method and
+//! redacted URL, so a replay works regardless of which credentials are used.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GetapiError, Result};
+use crate::http::{Request, Response};
+use crate::secrets::{self, REDACTED};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cassette {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Header names whose values are never written to disk.
+const SENSITIVE_HEADER_HINTS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "key",
+    "token",
+    "secret",
+    "password",
+    "signature",
+];
+
+/// JSON fields whose values are blanked in recorded responses.
+const SENSITIVE_FIELD_HINTS: &[&str] = &["token", "secret", "password", "key", "session"];
+
+pub fn save(dir: &Path, req: &Request, resp: &Response) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+
+    let cassette = Cassette {
+        request: RecordedRequest {
+            method: req.method.clone(),
+            url: redact_url(req),
+            headers: req
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), redact_header(req, name, value)))
+                .collect(),
+            body: req.body.as_ref().map(|b| redact_body(req, b)),
+        },
+        response: RecordedResponse {
+            status: resp.status,
+            body: redact_response_body(req, &resp.body),
+        },
+    };
+
+    let path = dir.join(file_name(req));
+    std::fs::write(&path, serde_json::to_string_pretty(&cassette)?)?;
+    Ok(path)
+}
+
+pub fn load(dir: &Path, req: &Request) -> Result<Response> {
+    let path = dir.join(file_name(req));
+    if !path.exists() {
+        return Err(GetapiError::Cassette(format!(
+            "No recorded response for {} {} (expected {}). Record one first with --record-http.",
+            req.method,
+            redact_url(req),
+            path.display()
+        )));
+    }
+
+    let contents = std::fs::read_to_string(&path)?;
+    let cassette: Cassette = serde_json::from_str(&contents).map_err(|e| {
+        GetapiError::Cassette(format!("Corrupt cassette {}: {}", path.display(), e))
+    })?;
+
+    Ok(Response {
+        status: cassette.response.status,
+        body: cassette.response.body,
+    })
+}
+
+/// `<method>-<host>-<hash>.json`, where the hash covers the method and redacted URL.
+fn file_name(req: &Request) -> String {
+    let url = redact_url(req);
+    let host = url
+        .split("://")
+        .nth(1)
+        .and_then(|rest| rest.split(['/', '?', ':']).next())
+        .unwrap_or("unknown");
+    let hash = secrets::fingerprint(&format!("{} {}", req.method.to_uppercase(), url));
+    format!("{}-{}-{}.json", req.method.to_lowercase(), host, hash)
+}
+
+fn is_sensitive(name: &str, hints: &[&str]) -> bool {
+    let name = name.to_lowercase();
+    hints.iter().any(|hint| name.contains(hint))
+}
+
+/// The URL with collected secrets and credential-like query parameters redacted.
+pub fn redact_url(req: &Request) -> String {
+    let url = secrets::scrub(&req.url, &req.secrets);
+    let Some((base, query)) = url.split_once('?') else {
+        return url;
+    };
+
+    let query: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if is_sensitive(name, SENSITIVE_HEADER_HINTS) => {
+                format!("{}={}", name, REDACTED)
+            }
+            _ => pair.to_string(),
+        })
+        .collect();
+    format!("{}?{}", base, query.join("&"))
+}
+
+/// A header value, redacted entirely for credential-bearing headers.
+pub fn redact_header(req: &Request, name: &str, value: &str) -> String {
+    if is_sensitive(name, SENSITIVE_HEADER_HINTS) {
+        REDACTED.to_string()
+    } else {
+        secrets::scrub(value, &req.secrets)
+    }
+}
+
+/// Form bodies keep their field names; every other body is scrubbed of known secrets.
+fn redact_body(req: &Request, body: &str) -> String {
+    let is_form = req.headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("content-type") && value.contains("x-www-form-urlencoded")
+    });
+    if !is_form {
+        return secrets::scrub(body, &req.secrets);
+    }
+
+    body.split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if name == "grant_type" || name == "scope" => pair.to_string(),
+            Some((name, _)) => format!("{}={}", name, REDACTED),
+            None => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// A response body with collected secrets and token-like JSON fields redacted.
+pub fn redact_response_body(req: &Request, body: &str) -> String {
+    let scrubbed = secrets::scrub(body, &req.secrets);
+    match serde_json::from_str::<serde_json::Value>(&scrubbed) {
+        Ok(mut json) => {
+            redact_json(&mut json);
+            json.to_string()
+        }
+        Err(_) => scrubbed,
+    }
+}
+
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, field) in map.iter_mut() {
+                if field.is_string() && is_sensitive(name, SENSITIVE_FIELD_HINTS) {
+                    *field = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> Request {
+        Request::get("https://api.telegram.org/bot123456:SECRETTOKEN/getMe?key=abc&q=1")
+            .header("Authorization", "Bearer 123456:SECRETTOKEN")
+            .header("Accept", "application/json")
+            .secrets(["123456:SECRETTOKEN"])
+    }
+
+    #[test]
+    fn test_round_trip_is_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let req = request();
+        let resp = Response {
+            status: 200,
+            body: r#"{"ok":true,"result":{"access_token":"xyz","username":"bot"}}"#.to_string(),
+        };
+
+        let path = save(dir.path(), &req, &resp).unwrap();
+        let written = std::fs::read_to_string(path).unwrap();
+        assert!(!written.contains("SECRETTOKEN"));
+        assert!(!written.contains("xyz"));
+        assert!(!written.contains("key=abc"));
+        assert!(written.contains("application/json"));
+
+        // Replays match regardless of the credential used
+        let other = Request::get("https://api.telegram.org/bot999999:OTHERTOKEN/getMe?key=def&q=1")
+            .secrets(["999999:OTHERTOKEN"]);
+        let replayed = load(dir.path(), &other).unwrap();
+        assert_eq!(replayed.status, 200);
+        assert!(replayed.body.contains("\"username\":\"bot\""));
+    }
+
+    #[test]
+    fn test_form_body_keeps_grant_type() {
+        let req = Request::post("https://example.com/token")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body("grant_type=client_credentials&client_secret=s3cr3t".to_string());
+        assert_eq!(
+            redact_body(&req, req.body.as_deref().unwrap()),
+            "grant_type=client_credentials&client_secret=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn test_missing_cassette() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(dir.path(), &request()).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! Logging of outbound HTTP requests to stderr, for `-v/--verbose` or `GETAPI_LOG`.
+//!
+//! Lines go through the same redaction as cassettes: collected secrets are scrubbed, values of
+//! credential-bearing headers are replaced, and token-like fields in JSON responses are blanked.
+//! Besides the secrets attached to each request, every value registered with `add_secrets` is
+//! scrubbed from every line.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use console::style;
+
+use crate::http::cassette;
+use crate::http::{Request, Response};
+
+/// Response bodies longer than this are cut short.
+const MAX_BODY_CHARS: usize = 500;
+
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+static SECRETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Turn logging on for this process (e.g. from `--verbose`).
+pub fn set_verbose(verbose: bool) {
+    VERBOSE.store(verbose, Ordering::Relaxed);
+}
+
+/// Whether requests are logged: `set_verbose`, or `GETAPI_LOG` set to anything but an empty
+/// string, `0`, `off` or `false`.
+pub fn enabled() -> bool {
+    VERBOSE.load(Ordering::Relaxed)
+        || std::env::var("GETAPI_LOG")
+            .is_ok_and(|v| !matches!(v.to_lowercase().as_str(), "" | "0" | "off" | "false"))
+}
+
+/// Scrub `secrets` from everything logged from now on, whichever request they turn up in.
+/// A validator only attaches the values it sends to its requests, so the runner registers
+/// every sensitive value it has collected here as well.
+pub fn add_secrets<I: IntoIterator<Item = String>>(secrets: I) {
+    let mut registered = SECRETS.lock().unwrap();
+    for secret in secrets {
+        if !registered.contains(&secret) {
+            registered.push(secret);
+        }
+    }
+}
+
+/// `req` with the registered secrets added to its own.
+fn with_registered(req: &Request) -> Request {
+    let mut req = req.clone();
+    req.secrets.extend(SECRETS.lock().unwrap().iter().cloned());
+    req
+}
+
+pub fn request(req: &Request) {
+    if !enabled() {
+        return;
+    }
+    for line in request_lines(&with_registered(req)) {
+        emit(&line);
+    }
+}
+
+pub fn response(req: &Request, resp: &Response, elapsed: Duration) {
+    if !enabled() {
+        return;
+    }
+    for line in response_lines(&with_registered(req), resp, elapsed) {
+        emit(&line);
+    }
+}
+
+/// Log a request that got no response at all.
+pub fn failure(req: &Request, error: &dyn std::fmt::Display, elapsed: Duration) {
+    if !enabled() {
+        return;
+    }
+    emit(&format!(
+        "✗ {} after {} ms",
+        crate::secrets::scrub(&error.to_string(), &with_registered(req).secrets),
+        elapsed.as_millis()
+    ));
+}
+
+fn emit(line: &str) {
+    eprintln!("  {} {}", style("[http]").dim(), line);
+}
+
+fn request_lines(req: &Request) -> Vec<String> {
+    let mut lines = vec![format!(
+        "→ {} {}",
+        req.method.to_uppercase(),
+        cassette::redact_url(req)
+    )];
+    lines.extend(
+        req.headers.iter().map(|(name, value)| {
+            format!("  {}: {}", name, cassette::redact_header(req, name, value))
+        }),
+    );
+    lines
+}
+
+fn response_lines(req: &Request, resp: &Response, elapsed: Duration) -> Vec<String> {
+    let mut lines = vec![format!(
+        "← {} in {} ms",
+        resp.status_line(),
+        elapsed.as_millis()
+    )];
+    let body = cassette::redact_response_body(req, &resp.body);
+    if !body.is_empty() {
+        lines.push(format!("  {}", truncate(&body)));
+    }
+    lines
+}
+
+fn truncate(body: &str) -> String {
+    let body = body.trim();
+    match body.char_indices().nth(MAX_BODY_CHARS) {
+        Some((cut, _)) => format!("{}… ({} more bytes)", &body[..cut], body.len() - cut),
+        None => body.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_are_scrubbed() {
+        let req = Request::get("https://api.telegram.org/bot123456:SECRETTOKEN/getMe")
+            .header("Authorization", "Bearer 123456:SECRETTOKEN")
+            .header("X-Custom", "prefix-123456:SECRETTOKEN")
+            .header("Accept", "application/json")
+            .secrets(["123456:SECRETTOKEN"]);
+        let resp = Response {
+            status: 401,
+            body: r#"{"error":"bad token 123456:SECRETTOKEN","access_token":"xyz"}"#.to_string(),
+        };
+
+        let mut lines = request_lines(&req);
+        lines.extend(response_lines(&req, &resp, Duration::from_millis(42)));
+        let log = lines.join("\n");
+        assert!(!log.contains("SECRETTOKEN"), "{}", log);
+        assert!(!log.contains("xyz"), "{}", log);
+        assert!(log.contains("Authorization: [REDACTED]"));
+        assert!(log.contains("Accept: application/json"));
+        assert!(log.contains("← 401 Unauthorized in 42 ms"));
+    }
+
+    #[test]
+    fn test_truncate() {
+        let long = "a".repeat(MAX_BODY_CHARS + 20);
+        assert_eq!(
+            truncate(&long),
+            format!("{}… (20 more bytes)", "a".repeat(MAX_BODY_CHARS))
+        );
+        assert_eq!(truncate(" short "), "short");
+    }
+
+    #[test]
+    fn test_registered_secrets_are_scrubbed() {
+        add_secrets(["registered-secret-value".to_string()]);
+        let req = Request::get("https://example.com/check?key=registered-secret-value");
+        let lines = request_lines(&with_registered(&req)).join("\n");
+        assert!(!lines.contains("registered-secret-value"), "{}", lines);
+    }
+}
@@ -0,0 +1,212 @@
+//! Shared HTTP layer for validators. Requests go through `send`, which can be pointed at canned
+//! responses instead of the network (see `set_mocks`), or record and replay cassettes on disk
+//! (see `Mode`). Every request can be logged with its redacted headers and response (see `log`).
+
+pub mod cassette;
+pub mod log;
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GetapiError, Result};
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    /// Values to scrub from anything written to disk or logged
+    pub secrets: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// The status code with its reason phrase, e.g. `401 Unauthorized`.
+    pub fn status_line(&self) -> String {
+        match reqwest::StatusCode::from_u16(self.status) {
+            Ok(status) => status.to_string(),
+            Err(_) => self.status.to_string(),
+        }
+    }
+}
+
+/// A canned response for requests matching `method` and `url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockResponse {
+    #[serde(default = "default_method")]
+    pub method: String,
+    pub url: String,
+    #[serde(default = "default_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub body: String,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+thread_local! {
+    static MOCKS: RefCell<Option<Vec<MockResponse>>> = const { RefCell::new(None) };
+}
+
+/// Serve requests on this thread from `mocks` instead of the network, until `clear_mocks`.
+pub fn set_mocks(mocks: Vec<MockResponse>) {
+    MOCKS.with(|m| *m.borrow_mut() = Some(mocks));
+}
+
+pub fn clear_mocks() {
+    MOCKS.with(|m| *m.borrow_mut() = None);
+}
+
+impl Request {
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+            secrets: Vec::new(),
+        }
+    }
+
+    pub fn post(url: &str) -> Self {
+        Self {
+            method: "POST".to_string(),
+            ..Self::get(url)
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn body(mut self, body: String) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn secrets<I, S>(mut self, secrets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.secrets.extend(secrets.into_iter().map(Into::into));
+        self
+    }
+}
+
+/// Where `send` gets its responses from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    /// Talk to the network
+    Live,
+    /// Talk to the network and save a redacted cassette per request in the directory
+    Record(PathBuf),
+    /// Serve responses from cassettes in the directory, never touching the network
+    Replay(PathBuf),
+}
+
+static MODE: Mutex<Option<Mode>> = Mutex::new(None);
+
+/// Override the mode for this process (e.g. from `--record-http` / `--replay-http`).
+pub fn set_mode(mode: Mode) {
+    *MODE.lock().unwrap_or_else(|e| e.into_inner()) = Some(mode);
+}
+
+/// The mode set with `set_mode`, falling back to the `GETAPI_RECORD_HTTP` and
+/// `GETAPI_REPLAY_HTTP` environment variables.
+pub fn mode() -> Mode {
+    if let Some(mode) = MODE.lock().unwrap_or_else(|e| e.into_inner()).clone() {
+        return mode;
+    }
+    if let Some(dir) = std::env::var_os("GETAPI_REPLAY_HTTP").filter(|d| !d.is_empty()) {
+        return Mode::Replay(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("GETAPI_RECORD_HTTP").filter(|d| !d.is_empty()) {
+        return Mode::Record(PathBuf::from(dir));
+    }
+    Mode::Live
+}
+
+pub fn send(req: &Request) -> Result<Response> {
+    log::request(req);
+    let started = Instant::now();
+    let result = dispatch(req);
+    match result {
+        Ok(ref resp) => log::response(req, resp, started.elapsed()),
+        Err(ref e) => log::failure(req, e, started.elapsed()),
+    }
+    result
+}
+
+fn dispatch(req: &Request) -> Result<Response> {
+    if let Some(result) = MOCKS.with(|m| m.borrow().as_ref().map(|mocks| mocked(mocks, req))) {
+        return result;
+    }
+
+    match mode() {
+        Mode::Live => send_live(req),
+        Mode::Replay(dir) => cassette::load(&dir, req),
+        Mode::Record(dir) => {
+            let resp = send_live(req)?;
+            cassette::save(&dir, req, &resp)?;
+            Ok(resp)
+        }
+    }
+}
+
+fn send_live(req: &Request) -> Result<Response> {
+    let client = reqwest::blocking::Client::new();
+    let method = reqwest::Method::from_bytes(req.method.as_bytes())
+        .map_err(|e| GetapiError::ValidationFailed(format!("Bad HTTP method: {}", e)))?;
+
+    let mut builder = client.request(method, &req.url);
+    for (name, value) in &req.headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(ref body) = req.body {
+        builder = builder.body(body.clone());
+    }
+
+    let resp = builder.send()?;
+    let status = resp.status().as_u16();
+    let body = resp.text().unwrap_or_default();
+
+    Ok(Response { status, body })
+}
+
+fn mocked(mocks: &[MockResponse], req: &Request) -> Result<Response> {
+    mocks
+        .iter()
+        .find(|m| m.method.eq_ignore_ascii_case(&req.method) && m.url == req.url)
+        .map(|m| Response {
+            status: m.status,
+            body: m.body.clone(),
+        })
+        .ok_or_else(|| {
+            GetapiError::ValidationFailed(format!(
+                "No mock response for {} {}",
+                req.method, req.url
+            ))
+        })
+}
@@ -0,0 +1,32 @@
+//! Ctrl-C handling for line-mode terminal runs.
+//!
+//! By default Ctrl-C kills the process mid-prompt, before the session can be saved. Once
+//! [`install`] has run, the first Ctrl-C is turned into a cancelled prompt instead, so the run
+//! is saved as paused; a second Ctrl-C still exits immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use signal_hook::consts::SIGINT;
+
+static INTERRUPTED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+/// Catch Ctrl-C for the rest of the process. Safe to call more than once.
+pub fn install() -> std::io::Result<()> {
+    if INTERRUPTED.get().is_some() {
+        return Ok(());
+    }
+    let flag = Arc::new(AtomicBool::new(false));
+    // Registered first, so it only sees the flag once an earlier Ctrl-C has set it
+    signal_hook::flag::register_conditional_shutdown(SIGINT, 130, Arc::clone(&flag))?;
+    signal_hook::flag::register(SIGINT, Arc::clone(&flag))?;
+    let _ = INTERRUPTED.set(flag);
+    Ok(())
+}
+
+/// Whether Ctrl-C has been pressed since [`install`].
+pub fn requested() -> bool {
+    INTERRUPTED
+        .get()
+        .is_some_and(|flag| flag.load(Ordering::SeqCst))
+}
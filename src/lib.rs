@@ -0,0 +1,60 @@
+//! Guided, interactive walkthroughs for setting up developer API credentials.
+//!
+//! This crate is the engine behind the `getapi` command-line tool. It can also be embedded to
+//! run recipes from other tools:
+//!
+//! - [`RecipeRegistry`] loads bundled, cached and local [`Recipe`]s.
+//! - [`run`] walks a recipe step by step and writes what it collected. All output and prompts
+//!   go through the [`Frontend`] in its [`RunContext`], so callers decide how steps are
+//!   presented and how answers are collected.
+//! - [`TerminalFrontend`] and [`ScriptedFrontend`] are the frontends the CLI uses for
+//!   interactive and `--answers` runs.
+//!
+//! ```no_run
+//! use getapi::{Answers, RecipeRegistry, RunContext, ScriptedFrontend};
+//!
+//! let registry = RecipeRegistry::new();
+//! let recipe = registry.find("openai").expect("bundled recipe");
+//!
+//! let answers = Answers::load("answers.json")?;
+//! let mut ctx = RunContext::builder(Box::new(ScriptedFrontend::new(answers)))
+//!     .output_file(".env")
+//!     .build();
+//! getapi::run(recipe, &mut ctx)?;
+//! println!("collected {:?}", ctx.collected.keys());
+//! # Ok::<(), getapi::GetapiError>(())
+//! ```
+
+#[doc(hidden)]
+pub mod cli;
+
+mod clipboard;
+mod error;
+mod frontend;
+mod harness;
+mod http;
+mod interrupt;
+mod manifest;
+mod output;
+mod recipe;
+mod rpc;
+mod runner;
+mod secrets;
+mod session;
+mod ui;
+mod validators;
+mod web;
+
+pub use error::{GetapiError, Result};
+pub use frontend::answers::{Answer, Answers};
+pub use frontend::scripted::ScriptedFrontend;
+pub use frontend::terminal::TerminalFrontend;
+pub use frontend::{Frontend, Message};
+pub use output::OutputFormat;
+pub use recipe::registry::RecipeRegistry;
+pub use recipe::types::{
+    Choice, ExtractPattern, FailureAction, Recipe, RecipeOutput, Step, StepCommon, Transform,
+};
+pub use runner::context::{Checkpoint, HistoryEntry, RunContext, RunContextBuilder};
+pub use runner::run;
+pub use validators::ValidationRecord;
@@ -6,6 +6,12 @@ pub struct Manifest {
     pub providers: Vec<ManifestProvider>,
     #[serde(default)]
     pub output: Option<ManifestOutput>,
+    /// Fill prompt_input steps from environment variables when the output key is already set
+    #[serde(default)]
+    pub use_env: bool,
+    /// Stage each credential in `.getapi/pending/` as soon as it's collected
+    #[serde(default)]
+    pub stage_credentials: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
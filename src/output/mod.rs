@@ -4,9 +4,31 @@ pub mod stdout;
 
 use std::collections::HashMap;
 
-use crate::cli::args::OutputFormat;
+use serde::Deserialize;
+
 use crate::error::Result;
 
+/// Where collected credentials are written.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Env,
+    Json,
+    Stdout,
+}
+
+impl OutputFormat {
+    /// The file written when no output path is given (empty for stdout).
+    pub fn default_file(&self) -> &'static str {
+        match self {
+            OutputFormat::Env => ".env",
+            OutputFormat::Json => "credentials.json",
+            OutputFormat::Stdout => "",
+        }
+    }
+}
+
+/// Write `values` to `file` in the given format. `.env` files keep keys they already had.
 pub fn write_output(
     format: &OutputFormat,
     file: &str,
@@ -0,0 +1,134 @@
+//! `depends_on` for validate steps: each entry names a step id, or an output key collected by
+//! a `prompt_input` step.
+
+use std::collections::HashMap;
+
+use crate::recipe::types::{Recipe, Step};
+
+/// The step a `depends_on` entry refers to: the step with that id, or the `prompt_input` step
+/// collecting that output key.
+pub fn resolve<'a>(recipe: &'a Recipe, dependency: &str) -> Option<&'a Step> {
+    recipe
+        .steps
+        .iter()
+        .find(|s| s.id() == dependency)
+        .or_else(|| {
+            recipe.steps.iter().find(
+                |s| matches!(s, Step::PromptInput { output_key, .. } if output_key == dependency),
+            )
+        })
+}
+
+/// The `prompt_input` steps that `depends_on` refers to, in recipe order.
+pub fn input_steps<'a>(recipe: &'a Recipe, depends_on: &[String]) -> Vec<&'a Step> {
+    recipe
+        .steps
+        .iter()
+        .filter(|s| matches!(s, Step::PromptInput { .. }))
+        .filter(|s| {
+            depends_on
+                .iter()
+                .any(|d| resolve(recipe, d).is_some_and(|r| r.id() == s.id()))
+        })
+        .collect()
+}
+
+/// The values `depends_on` refers to: the output keys of the `prompt_input` steps it names,
+/// and entries naming no step, which are taken to be keys themselves. Entries naming other
+/// steps have no value.
+pub fn value_keys(recipe: &Recipe, depends_on: &[String]) -> Vec<String> {
+    depends_on
+        .iter()
+        .filter_map(|dependency| match resolve(recipe, dependency) {
+            Some(Step::PromptInput { output_key, .. }) => Some(output_key.clone()),
+            Some(_) => None,
+            None => Some(dependency.clone()),
+        })
+        .collect()
+}
+
+/// The values `depends_on` needs that aren't in `values`. Entries naming a step that isn't a
+/// `prompt_input` can't be checked outside a walkthrough, so they count as met.
+pub fn missing_values(
+    recipe: &Recipe,
+    depends_on: &[String],
+    values: &HashMap<String, String>,
+) -> Vec<String> {
+    value_keys(recipe, depends_on)
+        .into_iter()
+        .filter(|key| !values.contains_key(key))
+        .collect()
+}
+
+/// Problems with the recipe's `depends_on` entries: names that match no step or output key,
+/// and steps that come after the validate step depending on them.
+pub fn problems(recipe: &Recipe) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (index, step) in recipe.steps.iter().enumerate() {
+        let Step::Validate { id, depends_on, .. } = step else {
+            continue;
+        };
+        for dependency in depends_on {
+            let declared = recipe.outputs.iter().any(|o| &o.key == dependency);
+            match resolve(recipe, dependency) {
+                Some(target) if recipe.find_step_index(target.id()) > Some(index) => problems.push(
+                    format!("{} depends on {}, which comes after it", id, dependency),
+                ),
+                Some(_) => {}
+                None if declared => {}
+                None => problems.push(format!(
+                    "{} depends on {}, which is neither a step nor an output key",
+                    id, dependency
+                )),
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recipe::loader;
+
+    #[test]
+    fn test_bundled_recipes_have_no_problems() {
+        for recipe in loader::load_all_bundled() {
+            assert_eq!(problems(&recipe), Vec::<String>::new(), "{}", recipe.id);
+        }
+    }
+
+    #[test]
+    fn test_resolve_output_key() {
+        let recipe = loader::load_all_bundled()
+            .into_iter()
+            .find(|r| r.id == "twitter")
+            .unwrap();
+        assert_eq!(
+            resolve(&recipe, "TWITTER_API_KEY").map(Step::id),
+            Some("collect_api_key")
+        );
+
+        let mut broken = recipe.clone();
+        for step in &mut broken.steps {
+            if let Step::Validate { depends_on, .. } = step {
+                depends_on.push("collect_nothing".to_string());
+            }
+        }
+        assert_eq!(problems(&broken).len(), 1);
+        assert!(loader::parse(&serde_json::to_string(&broken).unwrap()).is_err());
+
+        let values = HashMap::from([("TWITTER_API_KEY".to_string(), "key".to_string())]);
+        assert_eq!(
+            missing_values(
+                &recipe,
+                &[
+                    "collect_api_key".to_string(),
+                    "collect_api_secret".to_string()
+                ],
+                &values
+            ),
+            vec!["TWITTER_API_SECRET"]
+        );
+    }
+}
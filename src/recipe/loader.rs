@@ -2,21 +2,58 @@ use rust_embed::Embed;
 use std::path::Path;
 
 use crate::error::{GetapiError, Result};
-use crate::recipe::types::Recipe;
+use crate::recipe::types::{Recipe, Step};
+use crate::recipe::{deps, patterns};
 
 #[derive(Embed)]
 #[folder = "providers/"]
 struct BundledProviders;
 
+/// Parse a recipe, rejecting one whose `depends_on` entries don't resolve (see
+/// `deps::problems`) or whose `validation` specs don't compile, as well as malformed JSON.
+pub fn parse(json: &str) -> Result<Recipe> {
+    let recipe: Recipe =
+        serde_json::from_str(json).map_err(|e| GetapiError::InvalidRecipe(e.to_string()))?;
+    let mut problems = deps::problems(&recipe);
+    problems.extend(validation_problems(&recipe));
+    if !problems.is_empty() {
+        return Err(GetapiError::InvalidRecipe(format!(
+            "{}: {}",
+            recipe.id,
+            problems.join("; ")
+        )));
+    }
+    Ok(recipe)
+}
+
+/// `validation` specs that are unknown presets or bad regexes, so they're caught when the
+/// recipe loads rather than when the step runs.
+fn validation_problems(recipe: &Recipe) -> Vec<String> {
+    recipe
+        .steps
+        .iter()
+        .filter_map(|step| match step {
+            Step::PromptInput {
+                id,
+                validation: Some(spec),
+                ..
+            } => match patterns::compile(spec) {
+                Ok(_) => None,
+                Err(GetapiError::InvalidRecipe(reason)) => Some(format!("{}: {}", id, reason)),
+                Err(e) => Some(format!("{}: {}", id, e)),
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn load_from_file(path: &str) -> Result<Recipe> {
     let p = Path::new(path);
     if !p.exists() {
         return Err(GetapiError::RecipeFileNotFound(path.to_string()));
     }
     let contents = std::fs::read_to_string(p)?;
-    let recipe: Recipe =
-        serde_json::from_str(&contents).map_err(|e| GetapiError::InvalidRecipe(e.to_string()))?;
-    Ok(recipe)
+    parse(&contents)
 }
 
 pub fn load_all_bundled() -> Vec<Recipe> {
@@ -25,7 +62,7 @@ pub fn load_all_bundled() -> Vec<Recipe> {
         if filename.ends_with(".json") {
             if let Some(file) = BundledProviders::get(&filename) {
                 if let Ok(json) = std::str::from_utf8(&file.data) {
-                    if let Ok(recipe) = serde_json::from_str::<Recipe>(json) {
+                    if let Ok(recipe) = parse(json) {
                         recipes.push(recipe);
                     }
                 }
@@ -49,7 +86,7 @@ pub fn load_from_directory(dir: &str) -> Result<Vec<Recipe>> {
         let p = entry.path();
         if p.extension().and_then(|e| e.to_str()) == Some("json") {
             let contents = std::fs::read_to_string(&p)?;
-            match serde_json::from_str::<Recipe>(&contents) {
+            match parse(&contents) {
                 Ok(recipe) => recipes.push(recipe),
                 Err(e) => {
                     eprintln!("Warning: skipping invalid recipe {}: {}", p.display(), e);
@@ -59,3 +96,38 @@ pub fn load_from_directory(dir: &str) -> Result<Vec<Recipe>> {
     }
     Ok(recipes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_validation(spec: &str) -> String {
+        serde_json::json!({
+            "schema_version": "1",
+            "id": "demo",
+            "display_name": "Demo",
+            "description": "",
+            "outputs": [{ "key": "DEMO_KEY", "description": "" }],
+            "steps": [
+                { "id": "intro", "type": "info", "message": "" },
+                {
+                    "id": "collect_key",
+                    "type": "prompt_input",
+                    "message": "",
+                    "output_key": "DEMO_KEY",
+                    "validation": spec
+                }
+            ]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_bad_validation_rejected_at_load() {
+        assert!(parse(&with_validation("@hex:32")).is_ok());
+        for spec in ["@hexx:32", "@hex:abc", "^[a-z"] {
+            let err = parse(&with_validation(spec)).unwrap_err();
+            assert!(matches!(err, GetapiError::InvalidRecipe(ref m) if m.contains("collect_key")));
+        }
+    }
+}
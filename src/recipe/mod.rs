@@ -1,4 +1,6 @@
+pub mod deps;
 pub mod loader;
+pub mod patterns;
 pub mod registry;
 pub mod remote;
 pub mod template;
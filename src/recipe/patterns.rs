@@ -0,0 +1,222 @@
+use regex::Regex;
+
+use crate::error::{GetapiError, Result};
+
+const DEFAULT_ERROR: &str = "Input doesn't match the expected format. Please try again.";
+
+/// A compiled `validation` pattern, either a raw regex or a named preset.
+pub struct Pattern {
+    pub regex: Regex,
+    pub default_error: String,
+}
+
+impl Pattern {
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex.is_match(value)
+    }
+}
+
+/// Built-in presets usable as `"validation": "@name"` or `"validation": "@name:arg"`.
+///
+/// Length arguments accept `N` (exactly N), `N+` (at least N) or `N-M` (between N and M).
+const PRESETS: &[&str] = &[
+    "uuid", "jwt", "url", "email", "hex", "base64", "alnum", "digits", "prefix",
+];
+
+/// Compile a `validation` spec. Specs starting with `@` are resolved against the built-in
+/// presets; anything else is treated as a raw regex.
+pub fn compile(spec: &str) -> Result<Pattern> {
+    let Some(preset) = spec.strip_prefix('@') else {
+        let regex = Regex::new(spec)
+            .map_err(|e| GetapiError::InvalidRecipe(format!("Bad validation regex: {}", e)))?;
+        return Ok(Pattern {
+            regex,
+            default_error: DEFAULT_ERROR.to_string(),
+        });
+    };
+
+    let (name, arg) = match preset.split_once(':') {
+        Some((name, arg)) => (name, Some(arg)),
+        None => (preset, None),
+    };
+
+    let (pattern, default_error) = match name {
+        "uuid" => (
+            r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"
+                .to_string(),
+            "That doesn't look like a UUID (e.g. 123e4567-e89b-12d3-a456-426614174000)."
+                .to_string(),
+        ),
+        "jwt" => (
+            r"^eyJ[A-Za-z0-9_-]*\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]*$".to_string(),
+            "That doesn't look like a JWT. It should be three dot-separated parts starting with 'eyJ'."
+                .to_string(),
+        ),
+        "url" => (
+            r"^https?://[^\s/?#]+[^\s]*$".to_string(),
+            "That doesn't look like a URL. It should start with http:// or https://.".to_string(),
+        ),
+        "email" => (
+            r"^[^@\s]+@[^@\s]+\.[^@\s]+$".to_string(),
+            "That doesn't look like an email address.".to_string(),
+        ),
+        "hex" => {
+            let len = length(name, arg)?;
+            (
+                format!("^[0-9a-fA-F]{}$", len.quantifier),
+                format!("That doesn't look right. Expected {} hex characters.", len.describe),
+            )
+        }
+        "base64" => {
+            let len = length(name, arg)?;
+            (
+                format!("^[A-Za-z0-9+/_-]{}={{0,2}}$", len.quantifier),
+                format!(
+                    "That doesn't look right. Expected {} base64 characters.",
+                    len.describe
+                ),
+            )
+        }
+        "alnum" => {
+            let len = length(name, arg)?;
+            (
+                format!("^[A-Za-z0-9]{}$", len.quantifier),
+                format!(
+                    "That doesn't look right. Expected {} letters and digits.",
+                    len.describe
+                ),
+            )
+        }
+        "digits" => {
+            let len = length(name, arg)?;
+            (
+                format!("^[0-9]{}$", len.quantifier),
+                format!("That doesn't look right. Expected {} digits.", len.describe),
+            )
+        }
+        "prefix" => {
+            let prefix = arg.filter(|p| !p.is_empty()).ok_or_else(|| {
+                GetapiError::InvalidRecipe(
+                    "Validation preset '@prefix' needs a prefix, e.g. '@prefix:sk-'".to_string(),
+                )
+            })?;
+            (
+                format!(r"^{}\S+$", regex::escape(prefix)),
+                format!("That doesn't look right. It should start with '{}'.", prefix),
+            )
+        }
+        _ => {
+            return Err(GetapiError::InvalidRecipe(format!(
+                "Unknown validation preset '@{}'. Available presets: {}",
+                name,
+                PRESETS
+                    .iter()
+                    .map(|p| format!("@{}", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )))
+        }
+    };
+
+    let regex = Regex::new(&pattern)
+        .map_err(|e| GetapiError::InvalidRecipe(format!("Bad validation preset: {}", e)))?;
+
+    Ok(Pattern {
+        regex,
+        default_error,
+    })
+}
+
+struct Length {
+    quantifier: String,
+    describe: String,
+}
+
+fn length(name: &str, arg: Option<&str>) -> Result<Length> {
+    let bad = || {
+        GetapiError::InvalidRecipe(format!(
+            "Bad length '{}' for validation preset '@{}'. Use N, N+ or N-M.",
+            arg.unwrap_or_default(),
+            name
+        ))
+    };
+
+    let Some(arg) = arg else {
+        return Ok(Length {
+            quantifier: "+".to_string(),
+            describe: "only".to_string(),
+        });
+    };
+
+    if let Some(min) = arg.strip_suffix('+') {
+        let min: usize = min.parse().map_err(|_| bad())?;
+        return Ok(Length {
+            quantifier: format!("{{{},}}", min),
+            describe: format!("at least {}", min),
+        });
+    }
+
+    if let Some((min, max)) = arg.split_once('-') {
+        let min: usize = min.parse().map_err(|_| bad())?;
+        let max: usize = max.parse().map_err(|_| bad())?;
+        if min > max {
+            return Err(bad());
+        }
+        return Ok(Length {
+            quantifier: format!("{{{},{}}}", min, max),
+            describe: format!("{} to {}", min, max),
+        });
+    }
+
+    let exact: usize = arg.parse().map_err(|_| bad())?;
+    Ok(Length {
+        quantifier: format!("{{{}}}", exact),
+        describe: format!("exactly {}", exact),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_regex() {
+        let p = compile("^abc$").unwrap();
+        assert!(p.is_match("abc"));
+        assert!(!p.is_match("abcd"));
+    }
+
+    #[test]
+    fn test_hex_lengths() {
+        assert!(compile("@hex:32").unwrap().is_match(&"a".repeat(32)));
+        assert!(!compile("@hex:32").unwrap().is_match(&"a".repeat(31)));
+        assert!(compile("@hex:20+").unwrap().is_match(&"f".repeat(40)));
+        assert!(!compile("@hex:2-4").unwrap().is_match("abcde"));
+        assert!(!compile("@hex").unwrap().is_match("xyz"));
+    }
+
+    #[test]
+    fn test_prefix_is_escaped() {
+        let p = compile("@prefix:sk-ant-").unwrap();
+        assert!(p.is_match("sk-ant-api03-abc"));
+        assert!(!p.is_match("sk-ant-"));
+        assert!(!compile("@prefix:SG.").unwrap().is_match("SGxabc"));
+    }
+
+    #[test]
+    fn test_jwt_and_uuid() {
+        assert!(compile("@jwt")
+            .unwrap()
+            .is_match("eyJhbGciOi.eyJzdWIiOi.sig_-"));
+        assert!(compile("@uuid")
+            .unwrap()
+            .is_match("123e4567-e89b-12d3-a456-426614174000"));
+    }
+
+    #[test]
+    fn test_unknown_preset() {
+        assert!(compile("@nope").is_err());
+        assert!(compile("@hex:abc").is_err());
+        assert!(compile("@prefix").is_err());
+    }
+}
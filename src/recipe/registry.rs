@@ -4,10 +4,18 @@ use crate::recipe::loader;
 use crate::recipe::remote;
 use crate::recipe::types::Recipe;
 
+/// Every recipe getapi knows about: bundled ones, overridden by cached copies from
+/// `getapi update`, plus any added from a local directory.
 pub struct RecipeRegistry {
     recipes: Vec<Recipe>,
 }
 
+impl Default for RecipeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RecipeRegistry {
     pub fn new() -> Self {
         let bundled = loader::load_all_bundled();
@@ -1,8 +1,11 @@
 use std::path::PathBuf;
+use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::{GetapiError, Result};
+use crate::http;
+use crate::recipe::loader;
 use crate::recipe::types::Recipe;
 
 const BASE_URL: &str = "https://raw.githubusercontent.com/m2de/getapi/master/providers";
@@ -77,7 +80,7 @@ pub fn load_cached_recipes() -> Vec<Recipe> {
             continue;
         }
         if let Ok(contents) = std::fs::read_to_string(&path) {
-            match serde_json::from_str::<Recipe>(&contents) {
+            match loader::parse(&contents) {
                 Ok(recipe) => recipes.push(recipe),
                 Err(e) => {
                     eprintln!(
@@ -102,20 +105,17 @@ pub fn fetch_and_cache_all() -> Result<Vec<Recipe>> {
 
     // Fetch index
     let index_url = format!("{}/index.json", BASE_URL);
-    let index_resp = client
-        .get(&index_url)
-        .send()
+    let index_resp = fetch(&client, &index_url)
         .map_err(|e| GetapiError::RemoteFetch(format!("fetching index: {}", e)))?;
 
-    if !index_resp.status().is_success() {
+    if !index_resp.is_success() {
         return Err(GetapiError::RemoteFetch(format!(
             "index request returned {}",
-            index_resp.status()
+            index_resp.status_line()
         )));
     }
 
-    let index: RecipeIndex = index_resp
-        .json()
+    let index: RecipeIndex = serde_json::from_str(&index_resp.body)
         .map_err(|e| GetapiError::RemoteFetch(format!("parsing index: {}", e)))?;
 
     // Ensure cache dir exists
@@ -133,24 +133,19 @@ pub fn fetch_and_cache_all() -> Result<Vec<Recipe>> {
     let mut recipes = Vec::new();
     for entry in &index.recipes {
         let url = format!("{}/{}", BASE_URL, entry.file);
-        let resp = client
-            .get(&url)
-            .send()
+        let resp = fetch(&client, &url)
             .map_err(|e| GetapiError::RemoteFetch(format!("fetching {}: {}", entry.file, e)))?;
 
-        if !resp.status().is_success() {
+        if !resp.is_success() {
             return Err(GetapiError::RemoteFetch(format!(
                 "fetching {} returned {}",
                 entry.file,
-                resp.status()
+                resp.status_line()
             )));
         }
+        let body = resp.body;
 
-        let body = resp
-            .text()
-            .map_err(|e| GetapiError::RemoteFetch(format!("reading {}: {}", entry.file, e)))?;
-
-        let recipe: Recipe = serde_json::from_str(&body)
+        let recipe = loader::parse(&body)
             .map_err(|e| GetapiError::RemoteFetch(format!("parsing {}: {}", entry.file, e)))?;
 
         std::fs::write(dir.join(&entry.file), &body)
@@ -174,3 +169,20 @@ pub fn clear_cache() -> Result<()> {
     }
     Ok(())
 }
+
+/// GET `url` and read the whole body, logging the exchange when HTTP logging is on.
+fn fetch(client: &reqwest::blocking::Client, url: &str) -> reqwest::Result<http::Response> {
+    let req = http::Request::get(url).header("User-Agent", format!("getapi/{}", VERSION));
+    http::log::request(&req);
+    let started = Instant::now();
+
+    let result = client.get(url).send().and_then(|resp| {
+        let status = resp.status().as_u16();
+        resp.text().map(|body| http::Response { status, body })
+    });
+    match result {
+        Ok(ref resp) => http::log::response(&req, resp, started.elapsed()),
+        Err(ref e) => http::log::failure(&req, e, started.elapsed()),
+    }
+    result
+}
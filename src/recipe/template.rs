@@ -47,6 +47,14 @@ pub fn expand_lenient(template: &str, vars: &HashMap<String, String>) -> String
     result
 }
 
+/// The `{{var}}` names used in `template`, in order.
+pub fn names(template: &str) -> Vec<String> {
+    let re = Regex::new(r"\{\{(\w+)\}\}").unwrap();
+    re.captures_iter(template)
+        .map(|cap| cap[1].to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
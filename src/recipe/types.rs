@@ -1,7 +1,9 @@
-use serde::{Deserialize, Serialize};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Recipe {
     pub schema_version: String,
     pub id: String,
@@ -28,6 +30,7 @@ pub struct Recipe {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct RecipeOutput {
     pub key: String,
     pub description: String,
@@ -35,38 +38,65 @@ pub struct RecipeOutput {
     pub sensitive: bool,
 }
 
+/// Fields every step type accepts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct StepCommon {
+    /// How long the step usually takes, for the time-left estimate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_seconds: Option<u64>,
+    /// Offer to skip this step, e.g. for users who already have a project
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skippable: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Step {
     Info {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         message: String,
     },
     OpenUrl {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         url: String,
         message: String,
     },
     PromptConfirm {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         message: String,
     },
     PromptInput {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         message: String,
         output_key: String,
         #[serde(default)]
         validation: Option<String>,
         #[serde(default)]
         validation_error: Option<String>,
+        /// Normalizations applied to the pasted value before validation
+        #[serde(default)]
+        transform: Vec<Transform>,
     },
     PromptChoice {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         message: String,
         choices: Vec<Choice>,
     },
     Validate {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         method: String,
         message: String,
         #[serde(default)]
@@ -75,33 +105,52 @@ pub enum Step {
         on_success: Option<String>,
         #[serde(default)]
         on_failure: Option<String>,
+        /// What to do when validation fails; asks the user by default
+        #[serde(default)]
+        on_failure_action: FailureAction,
         /// Arbitrary config passed to the validator (e.g. url, header_name for http_get)
         #[serde(default)]
         config: HashMap<String, String>,
     },
     Output {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         message: String,
     },
     RunCommand {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         command: String,
         message: String,
     },
     Wait {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         message: String,
         #[serde(default)]
         resume_hint: Option<String>,
     },
     CopyToClipboard {
         id: String,
+        #[serde(flatten)]
+        common: StepCommon,
         value: String,
         message: String,
+        /// Sensitive values are cleared from the clipboard automatically. Unset, a value built
+        /// from a sensitive output is sensitive.
+        #[serde(default)]
+        sensitive: Option<bool>,
+        /// Seconds before the clipboard is cleared (0 disables auto-clear)
+        #[serde(default)]
+        clear_after: Option<u64>,
     },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Choice {
     pub label: String,
     #[serde(default)]
@@ -110,6 +159,65 @@ pub struct Choice {
     pub sets: Option<HashMap<String, String>>,
 }
 
+/// What happens after a validate step fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureAction {
+    /// Let the user pick one of the others
+    #[default]
+    Ask,
+    /// Go back to the `prompt_input` steps in `depends_on`
+    Reenter,
+    /// Run the validator again
+    Retry,
+    /// Keep the credentials and carry on
+    SaveAnyway,
+    /// Stop without writing anything
+    Abort,
+}
+
+/// A normalization applied to `prompt_input` values, written in recipes as either a bare
+/// string (`"trim"`) or a single-key object (`{ "strip_prefix": "Bearer " }`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    Trim,
+    StripPrefix(String),
+    StripQuotes,
+    RegexExtract(ExtractPattern),
+    Lowercase,
+    UrlPathSegment,
+}
+
+/// A `regex_extract` pattern, compiled when the recipe is loaded so a bad one makes the recipe
+/// invalid instead of failing once a value has been pasted.
+#[derive(Debug, Clone)]
+pub struct ExtractPattern(Regex);
+
+impl ExtractPattern {
+    pub fn new(pattern: &str) -> std::result::Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+
+    pub fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl Serialize for ExtractPattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtractPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern)
+            .map_err(|e| serde::de::Error::custom(format!("bad regex_extract pattern: {}", e)))
+    }
+}
+
 impl Step {
     pub fn id(&self) -> &str {
         match self {
@@ -125,10 +233,54 @@ impl Step {
             Step::CopyToClipboard { id, .. } => id,
         }
     }
+
+    /// Whether the step asks the user for an answer.
+    pub fn prompts(&self) -> bool {
+        matches!(
+            self,
+            Step::PromptInput { .. } | Step::PromptChoice { .. } | Step::PromptConfirm { .. }
+        )
+    }
+
+    /// Whether the user may skip the step.
+    pub fn skippable(&self) -> bool {
+        self.common().skippable
+    }
+
+    /// The fields shared by every step type.
+    pub fn common(&self) -> &StepCommon {
+        match self {
+            Step::Info { common, .. }
+            | Step::OpenUrl { common, .. }
+            | Step::PromptConfirm { common, .. }
+            | Step::PromptInput { common, .. }
+            | Step::PromptChoice { common, .. }
+            | Step::Validate { common, .. }
+            | Step::Output { common, .. }
+            | Step::RunCommand { common, .. }
+            | Step::Wait { common, .. }
+            | Step::CopyToClipboard { common, .. } => common,
+        }
+    }
+
+    /// How long the step usually takes, if the recipe says.
+    pub fn estimated_seconds(&self) -> Option<u64> {
+        self.common().estimated_seconds
+    }
 }
 
 impl Recipe {
+    /// Parse a recipe from its JSON, with the same checks as bundled and local recipes.
+    pub fn from_json(json: &str) -> crate::error::Result<Recipe> {
+        crate::recipe::loader::parse(json)
+    }
+
     pub fn find_step_index(&self, step_id: &str) -> Option<usize> {
         self.steps.iter().position(|s| s.id() == step_id)
     }
+
+    /// Whether `key` is declared as a sensitive output.
+    pub fn is_sensitive(&self, key: &str) -> bool {
+        self.outputs.iter().any(|o| o.key == key && o.sensitive)
+    }
 }
@@ -0,0 +1,115 @@
+use std::io::{BufRead, Write};
+
+use serde_json::{json, Value};
+
+use crate::error::{GetapiError, Result};
+use crate::rpc::types::Incoming;
+use crate::secrets;
+
+/// A newline-delimited JSON-RPC 2.0 connection. Every outgoing line is scrubbed of the secrets
+/// registered with `add_secret`, so collected credentials are never sent back to the client.
+pub struct Connection {
+    reader: Box<dyn BufRead>,
+    writer: Box<dyn Write>,
+    next_id: u64,
+    secrets: Vec<String>,
+}
+
+impl Connection {
+    pub fn new(reader: Box<dyn BufRead>, writer: Box<dyn Write>) -> Self {
+        Self {
+            reader,
+            writer,
+            next_id: 1,
+            secrets: Vec::new(),
+        }
+    }
+
+    pub fn add_secret(&mut self, value: &str) {
+        if !value.is_empty() && !self.secrets.iter().any(|s| s == value) {
+            self.secrets.push(value.to_string());
+        }
+    }
+
+    /// The next message from the client, or `None` once stdin is closed. Lines that aren't
+    /// valid JSON-RPC are answered with a parse error and skipped.
+    pub fn read(&mut self) -> Result<Option<Incoming>> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(message) => return Ok(Some(message)),
+                Err(e) => self.respond_error(Value::Null, PARSE_ERROR, &e.to_string())?,
+            }
+        }
+    }
+
+    pub fn notify(&mut self, method: &str, params: Value) -> Result<()> {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+
+    pub fn respond(&mut self, id: Value, result: Value) -> Result<()> {
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+    }
+
+    pub fn respond_error(&mut self, id: Value, code: i64, message: &str) -> Result<()> {
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }))
+    }
+
+    /// Ask the client something and block until it answers. Closing the connection, answering
+    /// with an error or sending a `cancel` notification cancels the run.
+    pub fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+
+        loop {
+            let Some(message) = self.read()? else {
+                return Err(GetapiError::UserCancelled);
+            };
+
+            match message.method.as_deref() {
+                Some("cancel") => return Err(GetapiError::UserCancelled),
+                Some(other) => {
+                    // Only one run at a time; other requests wait until it finishes
+                    if let Some(request_id) = message.id {
+                        let reason =
+                            format!("Cannot handle '{}' while a run is in progress.", other);
+                        self.respond_error(request_id, BUSY, &reason)?;
+                    }
+                }
+                None if message.id == Some(json!(id)) => {
+                    if message.error.is_some() {
+                        return Err(GetapiError::UserCancelled);
+                    }
+                    return Ok(message.result.unwrap_or(Value::Null));
+                }
+                None => {}
+            }
+        }
+    }
+
+    fn send(&mut self, message: Value) -> Result<()> {
+        let line = secrets::scrub(&message.to_string(), &self.secrets);
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// A run failed or was cancelled.
+pub const RUN_FAILED: i64 = -32000;
+/// A request arrived while a run was waiting on a prompt.
+pub const BUSY: i64 = -32001;
@@ -0,0 +1,157 @@
+//! `getapi serve --stdio`: drive recipes from an editor or another program over newline-delimited
+//! JSON-RPC 2.0 on stdin/stdout.
+//!
+//! The client sends `list` and `run` requests. While a recipe runs, the server sends
+//! notifications (`step_started`, `message`, `open_url`, `input_rejected`, `validation_result`,
+//! `completed`) and asks the client to answer prompts (`prompt_input`, `prompt_choice`,
+//! `prompt_confirm`, `prompt_wait`). See README.md for the full protocol.
+
+pub mod connection;
+pub mod types;
+
+use std::cell::RefCell;
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+use serde_json::{json, Value};
+
+use crate::error::{GetapiError, Result};
+use crate::frontend::rpc::RpcFrontend;
+use crate::output::OutputFormat;
+use crate::recipe::registry::RecipeRegistry;
+use crate::rpc::connection::{Connection, INVALID_PARAMS, METHOD_NOT_FOUND, RUN_FAILED};
+use crate::rpc::types::RunParams;
+use crate::runner;
+use crate::runner::context::RunContext;
+use crate::session;
+use crate::session::types::Session;
+
+/// Serve requests until the client sends `shutdown` or closes its end of the connection.
+pub fn serve(
+    registry: &RecipeRegistry,
+    reader: Box<dyn BufRead>,
+    writer: Box<dyn Write>,
+) -> Result<()> {
+    let conn = Rc::new(RefCell::new(Connection::new(reader, writer)));
+
+    loop {
+        let Some(message) = conn.borrow_mut().read()? else {
+            return Ok(());
+        };
+        // Notifications and stray responses need no answer
+        let (Some(id), Some(method)) = (message.id, message.method) else {
+            continue;
+        };
+
+        match method.as_str() {
+            "list" => {
+                let providers: Vec<Value> = registry
+                    .all()
+                    .iter()
+                    .map(|r| {
+                        json!({
+                            "id": r.id,
+                            "display_name": r.display_name,
+                            "description": r.description,
+                            "category": r.category,
+                        })
+                    })
+                    .collect();
+                conn.borrow_mut().respond(id, json!(providers))?;
+            }
+            "run" => {
+                let params = message.params.unwrap_or(Value::Null);
+                let params: RunParams = match serde_json::from_value(params) {
+                    // stdout is the protocol channel, so credentials can't be printed there
+                    Ok(RunParams {
+                        output: Some(OutputFormat::Stdout),
+                        ..
+                    }) => {
+                        let reason = "Output to stdout isn't available over --stdio.";
+                        conn.borrow_mut()
+                            .respond_error(id, INVALID_PARAMS, reason)?;
+                        continue;
+                    }
+                    Ok(params) => params,
+                    Err(e) => {
+                        conn.borrow_mut()
+                            .respond_error(id, INVALID_PARAMS, &e.to_string())?;
+                        continue;
+                    }
+                };
+                match run(registry, &params, &conn) {
+                    Ok(summary) => conn.borrow_mut().respond(id, summary)?,
+                    Err(e) => conn
+                        .borrow_mut()
+                        .respond_error(id, RUN_FAILED, &e.to_string())?,
+                }
+            }
+            "shutdown" => {
+                conn.borrow_mut().respond(id, Value::Null)?;
+                return Ok(());
+            }
+            other => {
+                let reason = format!("Unknown method '{}'.", other);
+                conn.borrow_mut()
+                    .respond_error(id, METHOD_NOT_FOUND, &reason)?;
+            }
+        }
+    }
+}
+
+/// Run one recipe through the RPC frontend, saving its session like a terminal run would.
+/// Returns the summary also sent as the `completed` notification.
+pub fn run(
+    registry: &RecipeRegistry,
+    params: &RunParams,
+    conn: &Rc<RefCell<Connection>>,
+) -> Result<Value> {
+    let recipe = registry
+        .find(&params.provider)
+        .ok_or_else(|| GetapiError::ProviderNotFound(params.provider.clone()))?;
+
+    let format = params.output.clone().unwrap_or(OutputFormat::Env);
+    let output_file = params
+        .output_file
+        .clone()
+        .unwrap_or_else(|| format.default_file().to_string());
+
+    let mut ctx = RunContext::builder(Box::new(RpcFrontend::new(conn.clone())))
+        .output_format(format.clone())
+        .output_file(output_file.clone())
+        .use_env(params.use_env)
+        .build();
+
+    let mut session = Session::new(
+        &recipe.id,
+        &output_file,
+        &format!("{:?}", format).to_lowercase(),
+    );
+    session.recipe_version = recipe.version.clone();
+    ctx.checkpoint = Some(session::store::checkpoint(&session, recipe));
+
+    let result = runner::run(recipe, &mut ctx);
+    match session::store::save_run(&mut session, recipe, &mut ctx, result) {
+        // A pause is reported in the summary, like one from a wait step
+        Ok(()) | Err(GetapiError::Paused) => {}
+        Err(e) => return Err(e),
+    }
+
+    let mut collected: Vec<&String> = ctx.collected.keys().collect();
+    collected.sort();
+    let summary = json!({
+        "provider": recipe.id,
+        "paused": ctx.paused,
+        "output_file": output_file,
+        "collected": collected,
+        "validations": ctx.validations.iter().map(|v| json!({
+            "step_id": v.step_id,
+            "passed": v.passed,
+            "skipped": v.skipped,
+            "error": v.error,
+            "identity": v.identity,
+        })).collect::<Vec<_>>(),
+    });
+    conn.borrow_mut().notify("completed", summary.clone())?;
+    Ok(summary)
+}
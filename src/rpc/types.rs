@@ -0,0 +1,31 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::output::OutputFormat;
+
+/// Any message from the client: a request, a notification or a response to a prompt.
+#[derive(Debug, Deserialize)]
+pub struct Incoming {
+    #[serde(default)]
+    pub id: Option<Value>,
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub params: Option<Value>,
+    #[serde(default)]
+    pub result: Option<Value>,
+    #[serde(default)]
+    pub error: Option<Value>,
+}
+
+/// Parameters of the `run` request.
+#[derive(Debug, Deserialize)]
+pub struct RunParams {
+    pub provider: String,
+    #[serde(default)]
+    pub output: Option<OutputFormat>,
+    #[serde(default)]
+    pub output_file: Option<String>,
+    #[serde(default)]
+    pub use_env: bool,
+}
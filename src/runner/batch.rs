@@ -0,0 +1,212 @@
+//! Validating the saved credentials of many providers at once, for `getapi validate --all`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use chrono::Duration;
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::manifest;
+use crate::output::env as env_output;
+use crate::recipe::registry::RecipeRegistry;
+use crate::runner::steps::validate::{self, SavedCheck};
+use crate::session;
+use crate::session::state::State;
+
+/// Providers validated at the same time.
+pub const MAX_WORKERS: usize = 4;
+
+/// A provider to validate and the file its credentials were written to.
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub provider: String,
+    pub output_file: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Failed,
+    Missing,
+    NoValidator,
+    /// No recipe matches the provider, so there was nothing to check against.
+    NotFound,
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Failed => "FAILED",
+            Status::Missing => "MISSING",
+            Status::NoValidator => "NO VALIDATOR",
+            Status::NotFound => "NOT FOUND",
+        }
+    }
+}
+
+/// The overall outcome for one provider.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderResult {
+    pub provider: String,
+    pub output_file: String,
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub checks: Vec<SavedCheck>,
+}
+
+/// Providers with a session, then any others the project manifest lists. Sessions know where
+/// their credentials went; manifest providers use the manifest's output file.
+pub fn discover() -> Result<Vec<Target>> {
+    let mut targets: Vec<Target> = session::store::list_all()?
+        .into_iter()
+        .map(|s| Target {
+            provider: s.provider,
+            output_file: s.output_file,
+        })
+        .collect();
+    targets.sort_by(|a, b| a.provider.cmp(&b.provider));
+
+    if let Some(manifest) = manifest::loader::load()? {
+        let output_file = manifest
+            .output
+            .map(|o| o.file)
+            .unwrap_or_else(|| ".env".to_string());
+        for provider in manifest.providers {
+            if !targets.iter().any(|t| t.provider == provider.id) {
+                targets.push(Target {
+                    provider: provider.id,
+                    output_file: output_file.clone(),
+                });
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// Validate every target on up to `workers` threads. Results come back in target order.
+/// With `max_age`, checks that passed more recently than that, according to `state`, are
+/// skipped. Fails if any check hits a missing cassette (see `validate::run`).
+pub fn run(
+    registry: &RecipeRegistry,
+    targets: &[Target],
+    workers: usize,
+    state: &State,
+    max_age: Option<Duration>,
+) -> Result<Vec<ProviderResult>> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<ProviderResult>>>> =
+        Mutex::new((0..targets.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, targets.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(target) = targets.get(index) else {
+                    break;
+                };
+                let result = check(registry, target, state, max_age);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Validate one provider's saved credentials.
+pub fn check(
+    registry: &RecipeRegistry,
+    target: &Target,
+    state: &State,
+    max_age: Option<Duration>,
+) -> Result<ProviderResult> {
+    let mut result = ProviderResult {
+        provider: target.provider.clone(),
+        output_file: target.output_file.clone(),
+        status: Status::Ok,
+        error: None,
+        checks: Vec::new(),
+    };
+
+    let Some(recipe) = registry.find(&target.provider) else {
+        result.status = Status::NotFound;
+        result.error = Some("no recipe for this provider".to_string());
+        return Ok(result);
+    };
+
+    let existing = env_output::read_existing(&target.output_file);
+    if !recipe.outputs.is_empty()
+        && recipe
+            .outputs
+            .iter()
+            .all(|o| !existing.contains_key(&o.key))
+    {
+        result.status = Status::Missing;
+        result.error = Some(format!("nothing saved in {}", target.output_file));
+        return Ok(result);
+    }
+
+    let entries = state.providers.get(&recipe.id);
+    let skip_recent = entries.zip(max_age);
+    result.checks = validate::check_saved(recipe, &existing, skip_recent)?;
+    result.status = if result.checks.is_empty() {
+        Status::NoValidator
+    } else if result
+        .checks
+        .iter()
+        .any(|c| matches!(c, SavedCheck::Ran(r) if !r.passed))
+    {
+        Status::Failed
+    } else if result.checks.iter().all(SavedCheck::passed) {
+        Status::Ok
+    } else {
+        Status::Missing
+    };
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_keeps_target_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "AUTH0_DOMAIN=example.auth0.com\n").unwrap();
+        let output_file = env_file.to_string_lossy().to_string();
+
+        let targets: Vec<Target> = ["openai", "auth0", "nope", "twitter", "firebase"]
+            .iter()
+            .map(|provider| Target {
+                provider: provider.to_string(),
+                output_file: output_file.clone(),
+            })
+            .collect();
+        let results = run(&RecipeRegistry::new(), &targets, 2, &State::default(), None).unwrap();
+
+        let statuses: Vec<(&str, Status)> = results
+            .iter()
+            .map(|r| (r.provider.as_str(), r.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("openai", Status::Missing),
+                ("auth0", Status::NoValidator),
+                ("nope", Status::NotFound),
+                ("twitter", Status::Missing),
+                ("firebase", Status::Missing),
+            ]
+        );
+    }
+}
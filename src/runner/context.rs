@@ -1,7 +1,28 @@
 use std::collections::HashMap;
 
-use crate::cli::args::OutputFormat;
+use crate::error::Result;
+use crate::frontend::Frontend;
+use crate::output::OutputFormat;
+use crate::validators::ValidationRecord;
 
+/// The state from just before a completed step ran, so going back can undo it.
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub step_index: usize,
+    pub step_id: String,
+    /// Whether the step asks the user something (and so is worth going back to)
+    pub prompts: bool,
+    vars: HashMap<String, String>,
+    collected: HashMap<String, String>,
+    choices_made: HashMap<String, String>,
+    validations: usize,
+}
+
+/// Called after every step so progress survives a crash or a closed terminal.
+pub type Checkpoint = Box<dyn FnMut(&RunContext) -> Result<()>>;
+
+/// Everything a run reads and records. Build one with [`RunContext::builder`].
+#[non_exhaustive]
 pub struct RunContext {
     /// Template variables (from choices, etc.)
     pub vars: HashMap<String, String>,
@@ -9,43 +30,73 @@ pub struct RunContext {
     pub collected: HashMap<String, String>,
     /// Steps that have been completed (by id)
     pub completed_steps: Vec<String>,
+    /// Steps the user chose to skip (by id)
+    pub skipped_steps: Vec<String>,
+    /// Steps to skip without asking (`--skip`)
+    pub skip: Vec<String>,
+    /// Steps executed during this run, in order
+    pub visited: Vec<String>,
+    /// Outcomes of validate steps run during this run
+    pub validations: Vec<ValidationRecord>,
     /// Choices made (step_id → chosen label)
     pub choices_made: HashMap<String, String>,
+    /// Steps completed during this run, most recent last, for going back
+    pub history: Vec<HistoryEntry>,
     /// Output format
     pub output_format: OutputFormat,
     /// Output file path
     pub output_file: String,
     /// Whether running in non-interactive mode
     pub non_interactive: bool,
+    /// Whether prompt_input steps may be filled from process environment variables
+    pub use_env: bool,
+    /// Fail instead of saving credentials that didn't validate (`--strict`)
+    pub strict: bool,
+    /// Stage collected credentials after every step, not only when paused (`--stage-credentials`)
+    pub stage_credentials: bool,
+    /// Where output goes and answers come from
+    pub frontend: Box<dyn Frontend>,
     /// Current step index (0-based)
     pub current_step_index: usize,
-    /// Total number of steps
-    pub total_steps: usize,
     /// Whether the session was paused (hit a wait step)
     pub paused: bool,
     /// The step id to jump to (from a choice with `next`)
     pub jump_to: Option<String>,
+    /// Completed steps to run again after the current one, along with `jump_to`
+    pub revisit: Vec<String>,
+    /// Saves progress after each step, if set
+    pub checkpoint: Option<Checkpoint>,
 }
 
 impl RunContext {
-    pub fn new(
-        output_format: OutputFormat,
-        output_file: String,
-        non_interactive: bool,
-        total_steps: usize,
-    ) -> Self {
-        Self {
-            vars: HashMap::new(),
-            collected: HashMap::new(),
-            completed_steps: Vec::new(),
-            choices_made: HashMap::new(),
-            output_format,
-            output_file,
-            non_interactive,
-            current_step_index: 0,
-            total_steps,
-            paused: false,
-            jump_to: None,
+    /// Start building a context that runs through `frontend`. Credentials go to `.env` unless
+    /// the builder says otherwise.
+    pub fn builder(frontend: Box<dyn Frontend>) -> RunContextBuilder {
+        RunContextBuilder {
+            ctx: Self {
+                vars: HashMap::new(),
+                collected: HashMap::new(),
+                completed_steps: Vec::new(),
+                skipped_steps: Vec::new(),
+                skip: Vec::new(),
+                visited: Vec::new(),
+                validations: Vec::new(),
+                choices_made: HashMap::new(),
+                history: Vec::new(),
+                output_format: OutputFormat::Env,
+                output_file: String::new(),
+                non_interactive: false,
+                use_env: false,
+                strict: false,
+                stage_credentials: false,
+                frontend,
+                current_step_index: 0,
+                paused: false,
+                jump_to: None,
+                revisit: Vec::new(),
+                checkpoint: None,
+            },
+            output_file: None,
         }
     }
 
@@ -57,6 +108,12 @@ impl RunContext {
         self.collected.insert(key, value);
     }
 
+    /// Record a sensitive collected value, telling the frontend so it never echoes it back.
+    pub fn set_secret(&mut self, key: String, value: String) {
+        self.frontend.add_secret(&value);
+        self.set_collected(key, value);
+    }
+
     pub fn mark_completed(&mut self, step_id: &str) {
         if !self.completed_steps.contains(&step_id.to_string()) {
             self.completed_steps.push(step_id.to_string());
@@ -66,4 +123,125 @@ impl RunContext {
     pub fn is_completed(&self, step_id: &str) -> bool {
         self.completed_steps.contains(&step_id.to_string())
     }
+
+    pub fn mark_skipped(&mut self, step_id: &str) {
+        if !self.is_skipped(step_id) {
+            self.skipped_steps.push(step_id.to_string());
+        }
+    }
+
+    pub fn is_skipped(&self, step_id: &str) -> bool {
+        self.skipped_steps.contains(&step_id.to_string())
+    }
+
+    /// Run the checkpoint, if any. A failure is reported but doesn't stop the run.
+    pub fn save_checkpoint(&mut self) {
+        let Some(mut checkpoint) = self.checkpoint.take() else {
+            return;
+        };
+        if let Err(e) = checkpoint(self) {
+            self.frontend
+                .warning(&format!("Couldn't save progress: {}", e));
+        }
+        self.checkpoint = Some(checkpoint);
+    }
+
+    /// Capture the state before step `step_index` runs. Pushed onto `history` once it completes.
+    pub fn snapshot(&self, step_index: usize, step_id: &str, prompts: bool) -> HistoryEntry {
+        HistoryEntry {
+            step_index,
+            step_id: step_id.to_string(),
+            prompts,
+            vars: self.vars.clone(),
+            collected: self.collected.clone(),
+            choices_made: self.choices_made.clone(),
+            validations: self.validations.len(),
+        }
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.history.iter().any(|entry| entry.prompts)
+    }
+
+    /// Rewind to the most recent step that asked the user something, undoing it and every step
+    /// after it. Returns the index to run next, or `None` if there's nothing to go back to.
+    pub fn go_back(&mut self) -> Option<usize> {
+        let position = self.history.iter().rposition(|entry| entry.prompts)?;
+        let undone = self.history.split_off(position);
+
+        for entry in &undone {
+            self.completed_steps.retain(|id| id != &entry.step_id);
+            self.skipped_steps.retain(|id| id != &entry.step_id);
+        }
+
+        let target = &undone[0];
+        self.vars = target.vars.clone();
+        self.collected = target.collected.clone();
+        self.choices_made = target.choices_made.clone();
+        self.validations.truncate(target.validations);
+        Some(target.step_index)
+    }
+}
+
+/// Builds a [`RunContext`]. Every setting is optional.
+pub struct RunContextBuilder {
+    ctx: RunContext,
+    output_file: Option<String>,
+}
+
+impl RunContextBuilder {
+    /// The format to write collected credentials in (default: `.env`).
+    pub fn output_format(mut self, format: OutputFormat) -> Self {
+        self.ctx.output_format = format;
+        self
+    }
+
+    /// Where to write them (default: the format's usual file).
+    pub fn output_file(mut self, file: impl Into<String>) -> Self {
+        self.output_file = Some(file.into());
+        self
+    }
+
+    /// Print steps as a checklist without prompting.
+    pub fn non_interactive(mut self, non_interactive: bool) -> Self {
+        self.ctx.non_interactive = non_interactive;
+        self
+    }
+
+    /// Fill prompt_input steps from environment variables.
+    pub fn use_env(mut self, use_env: bool) -> Self {
+        self.ctx.use_env = use_env;
+        self
+    }
+
+    /// Fail instead of saving credentials that didn't validate.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.ctx.strict = strict;
+        self
+    }
+
+    /// Stage collected credentials after every step.
+    pub fn stage_credentials(mut self, stage: bool) -> Self {
+        self.ctx.stage_credentials = stage;
+        self
+    }
+
+    /// Steps to skip without asking.
+    pub fn skip(mut self, steps: Vec<String>) -> Self {
+        self.ctx.skip = steps;
+        self
+    }
+
+    /// Save progress after every step.
+    pub fn checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.ctx.checkpoint = Some(checkpoint);
+        self
+    }
+
+    pub fn build(mut self) -> RunContext {
+        self.ctx.output_file = self
+            .output_file
+            .unwrap_or_else(|| self.ctx.output_format.default_file().to_string());
+        self.ctx
+    }
 }
@@ -1,20 +1,44 @@
+pub mod batch;
 pub mod context;
+pub mod path;
 pub mod steps;
+pub mod transform;
 
 use crate::error::{GetapiError, Result};
+use crate::frontend::Message;
+use crate::recipe::template;
 use crate::recipe::types::{Recipe, Step};
 use crate::runner::context::RunContext;
-use crate::ui;
 
+/// Walk `recipe` from `ctx.current_step_index`, prompting through `ctx.frontend`, and write the
+/// collected values to `ctx.output_file` once every step has run. Returns early with
+/// `ctx.paused` set when a wait step pauses the run.
 pub fn run(recipe: &Recipe, ctx: &mut RunContext) -> Result<()> {
-    ui::print_header(&recipe.display_name, &recipe.description);
+    ctx.frontend.print(Message::Header {
+        name: &recipe.display_name,
+        description: &recipe.description,
+    });
 
     if let Some(ref time) = recipe.estimated_time {
-        ui::print_meta("Estimated time", time);
+        ctx.frontend.print(Message::Meta {
+            label: "Estimated time",
+            value: time,
+        });
     }
 
     if !recipe.prerequisites.is_empty() {
-        ui::print_meta("Prerequisites", &recipe.prerequisites.join(", "));
+        ctx.frontend.print(Message::Meta {
+            label: "Prerequisites",
+            value: &recipe.prerequisites.join(", "),
+        });
+    }
+
+    for id in &ctx.skip {
+        match recipe.steps.iter().find(|s| s.id() == id) {
+            Some(step) if step.skippable() => {}
+            Some(_) => return Err(GetapiError::NotSkippable(id.clone())),
+            None => return Err(GetapiError::StepNotFound(id.clone())),
+        }
     }
 
     let mut i = ctx.current_step_index;
@@ -24,16 +48,57 @@ pub fn run(recipe: &Recipe, ctx: &mut RunContext) -> Result<()> {
         let step_id = step.id().to_string();
 
         // Skip completed steps (for resume)
-        if ctx.is_completed(&step_id) {
+        if ctx.is_completed(&step_id) || ctx.is_skipped(&step_id) {
             i += 1;
             continue;
         }
 
         ctx.current_step_index = i;
-        let step_num = i + 1;
-        ui::print_step_counter(step_num, ctx.total_steps);
+        // Count along the path the choices so far lead down, not every step in the recipe
+        let route = path::reachable(recipe, 0, &ctx.choices_made);
+        let (current, total, remaining) = match route.iter().position(|&s| s == i) {
+            Some(pos) => (
+                pos + 1,
+                route.len(),
+                path::estimated_seconds(recipe, &route[pos..]),
+            ),
+            None => (i + 1, recipe.steps.len(), None),
+        };
+        ctx.frontend.print(Message::StepCounter {
+            step_id: &step_id,
+            current,
+            total,
+            remaining,
+        });
 
-        execute_step(step, ctx)?;
+        ctx.visited.push(step_id.clone());
+        let offer_skip = step.skippable() && !ctx.non_interactive && !ctx.skip.contains(&step_id);
+        let before = ctx.snapshot(i, &step_id, step.prompts() || offer_skip);
+        let can_go_back = ctx.can_go_back();
+        ctx.frontend.set_can_go_back(can_go_back);
+
+        let skipped = match skip_or_execute(recipe, step, offer_skip, ctx) {
+            Err(GetapiError::GoBack) => {
+                match ctx.go_back() {
+                    Some(previous) => i = previous,
+                    None => ctx
+                        .frontend
+                        .warning("There's no earlier step to go back to."),
+                }
+                continue;
+            }
+            result => result?,
+        };
+
+        if skipped {
+            ctx.frontend
+                .print(Message::StepSkipped { step_id: &step_id });
+            ctx.mark_skipped(&step_id);
+            ctx.history.push(before);
+            ctx.save_checkpoint();
+            i += 1;
+            continue;
+        }
 
         // If paused (wait step), break out of the loop
         if ctx.paused {
@@ -41,6 +106,12 @@ pub fn run(recipe: &Recipe, ctx: &mut RunContext) -> Result<()> {
         }
 
         ctx.mark_completed(&step_id);
+        ctx.history.push(before);
+        // Steps to run again, e.g. inputs to re-enter after a failed validation
+        for id in std::mem::take(&mut ctx.revisit) {
+            ctx.completed_steps.retain(|done| done != &id);
+        }
+        ctx.save_checkpoint();
 
         // Handle jump (from choice with `next`)
         if let Some(ref target) = ctx.jump_to.take() {
@@ -60,22 +131,24 @@ pub fn run(recipe: &Recipe, ctx: &mut RunContext) -> Result<()> {
 
     // If we completed all steps and have collected values, write output
     if !ctx.collected.is_empty() && !ctx.paused {
-        println!();
+        ctx.frontend.print(Message::Blank);
         crate::output::write_output(&ctx.output_format, &ctx.output_file, &ctx.collected)?;
-        ui::print_success(&format!("Credentials written to {}", ctx.output_file));
+        ctx.frontend
+            .success(&format!("Credentials written to {}", ctx.output_file));
     }
 
     if !ctx.paused {
         if ctx.collected.is_empty() {
-            println!();
+            ctx.frontend.print(Message::Blank);
         }
-        ui::print_success(&format!("{} setup complete!", recipe.display_name));
+        ctx.frontend
+            .success(&format!("{} setup complete!", recipe.display_name));
 
         if !recipe.gotchas.is_empty() {
-            println!();
-            ui::print_section("Things to know");
+            ctx.frontend.print(Message::Blank);
+            ctx.frontend.print(Message::Section("Things to know"));
             for gotcha in &recipe.gotchas {
-                ui::print_bullet(gotcha);
+                ctx.frontend.print(Message::Bullet(gotcha));
             }
         }
     }
@@ -83,55 +156,466 @@ pub fn run(recipe: &Recipe, ctx: &mut RunContext) -> Result<()> {
     Ok(())
 }
 
-fn execute_step(step: &Step, ctx: &mut RunContext) -> Result<()> {
+/// Run `step` unless it's skipped, either with `--skip` or by the user when `offer_skip` is set.
+/// Returns whether it was skipped.
+fn skip_or_execute(
+    recipe: &Recipe,
+    step: &Step,
+    offer_skip: bool,
+    ctx: &mut RunContext,
+) -> Result<bool> {
+    let skip = ctx.skip.iter().any(|id| id == step.id())
+        || (offer_skip && ctx.frontend.offer_skip(step.id())?);
+    if !skip {
+        execute_step(recipe, step, ctx)?;
+    }
+    Ok(skip)
+}
+
+fn execute_step(recipe: &Recipe, step: &Step, ctx: &mut RunContext) -> Result<()> {
     match step {
         Step::Info { message, .. } => steps::info::handle(message, ctx),
-        Step::OpenUrl { url, message, .. } => steps::open_url::handle(url, message, ctx),
-        Step::PromptConfirm { message, .. } => steps::prompt_confirm::handle(message, ctx),
+        Step::OpenUrl {
+            id, url, message, ..
+        } => steps::open_url::handle(id, url, message, ctx),
+        Step::PromptConfirm { id, message, .. } => steps::prompt_confirm::handle(id, message, ctx),
         Step::PromptInput {
+            id,
             message,
             output_key,
             validation,
             validation_error,
+            transform,
             ..
         } => steps::prompt_input::handle(
+            id,
             message,
             output_key,
             validation.as_deref(),
             validation_error.as_deref(),
+            transform,
+            recipe.is_sensitive(output_key),
             ctx,
         ),
         Step::PromptChoice {
             id,
             message,
             choices,
-        } => steps::prompt_choice::handle(id, message, choices, ctx),
-        Step::Validate {
-            method,
-            message,
-            on_success,
-            on_failure,
-            config,
             ..
-        } => steps::validate::handle(
-            method,
-            message,
-            on_success.as_deref(),
-            on_failure.as_deref(),
-            config,
-            ctx,
-        ),
+        } => steps::prompt_choice::handle(id, message, choices, ctx),
+        Step::Validate { .. } => steps::validate::handle(recipe, step, ctx),
         Step::Output { message, .. } => steps::output::handle(message, ctx),
         Step::RunCommand {
-            command, message, ..
-        } => steps::run_command::handle(command, message, ctx),
+            id,
+            command,
+            message,
+            ..
+        } => steps::run_command::handle(id, command, message, ctx),
         Step::Wait {
+            id,
             message,
             resume_hint,
             ..
-        } => steps::wait::handle(message, resume_hint.as_deref(), ctx),
-        Step::CopyToClipboard { value, message, .. } => {
-            steps::copy_clipboard::handle(value, message, ctx)
+        } => steps::wait::handle(id, message, resume_hint.as_deref(), ctx),
+        Step::CopyToClipboard {
+            value,
+            message,
+            sensitive,
+            clear_after,
+            ..
+        } => {
+            let sensitive = sensitive.unwrap_or_else(|| {
+                template::names(value)
+                    .iter()
+                    .any(|key| recipe.is_sensitive(key))
+            });
+            steps::copy_clipboard::handle(value, message, sensitive, *clear_after, ctx)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::frontend::answers::Answers;
+    use crate::frontend::scripted::ScriptedFrontend;
+    use crate::frontend::Frontend;
+    use crate::recipe::loader;
+    use crate::recipe::types::FailureAction;
+
+    /// The bundled twitter recipe, without its validate step so tests stay offline.
+    fn twitter() -> Recipe {
+        let mut recipe = loader::load_all_bundled()
+            .into_iter()
+            .find(|r| r.id == "twitter")
+            .unwrap();
+        recipe.steps.retain(|s| !matches!(s, Step::Validate { .. }));
+        recipe
+    }
+
+    fn scripted_ctx(answers: &str, output_file: &str) -> RunContext {
+        let answers: Answers = serde_json::from_str(answers).unwrap();
+        RunContext::builder(Box::new(ScriptedFrontend::new(answers)))
+            .output_file(output_file)
+            .build()
+    }
+
+    #[test]
+    fn test_twitter_dashboard_path() {
+        let recipe = twitter();
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        let mut ctx = scripted_ctx(
+            r#"{ "answers": {
+                "check_access": "The developer dashboard",
+                "confirm_project": true,
+                "scope_choice": "Post tweets",
+                "collect_api_key": "abcdefghijklmnopqrstuvwxyz",
+                "collect_api_secret": "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                "collect_bearer_token": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA%3D"
+            } }"#,
+            env_file.to_str().unwrap(),
+        );
+
+        run(&recipe, &mut ctx).unwrap();
+
+        assert!(!ctx.paused);
+        assert!(!ctx.is_completed("apply_access"));
+        assert_eq!(ctx.choices_made["scope_choice"], "Post tweets");
+        assert!(ctx.vars["scope_note"].contains("Read and Write"));
+        assert_eq!(ctx.collected.len(), 3);
+
+        let written = std::fs::read_to_string(&env_file).unwrap();
+        assert!(written.contains("TWITTER_API_KEY=abcdefghijklmnopqrstuvwxyz\n"));
+    }
+
+    #[test]
+    fn test_twitter_application_path_needs_wait_answer() {
+        let recipe = twitter();
+        let answers = r#"{ "answers": { "check_access": "A sign-up or application page" } }"#;
+        let mut ctx = scripted_ctx(answers, "");
+
+        let err = run(&recipe, &mut ctx).unwrap_err();
+        assert!(matches!(err, GetapiError::MissingAnswer(ref id) if id == "apply_access"));
+    }
+
+    /// Goes back once when `at` first prompts, answering everything else from the script.
+    struct BackOnce {
+        inner: ScriptedFrontend,
+        at: &'static str,
+        went_back: bool,
+    }
+
+    impl BackOnce {
+        fn back(&mut self, step_id: &str) -> Result<()> {
+            if step_id == self.at && !self.went_back {
+                self.went_back = true;
+                return Err(GetapiError::GoBack);
+            }
+            Ok(())
+        }
+    }
+
+    impl Frontend for BackOnce {
+        fn print(&mut self, message: Message) {
+            self.inner.print(message)
+        }
+        fn confirm(&mut self, step_id: &str, prompt: &str, default: bool) -> Result<bool> {
+            self.back(step_id)?;
+            self.inner.confirm(step_id, prompt, default)
+        }
+        fn select(&mut self, id: &str, prompt: &str, items: &[&str], d: usize) -> Result<usize> {
+            self.back(id)?;
+            self.inner.select(id, prompt, items, d)
+        }
+        fn input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+            self.back(step_id)?;
+            self.inner.input(step_id, prompt)
+        }
+        fn secret_input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+            self.back(step_id)?;
+            self.inner.secret_input(step_id, prompt)
+        }
+        fn reject_input(&mut self, step_id: &str, reason: &str) -> Result<()> {
+            self.inner.reject_input(step_id, reason)
+        }
+        fn open_url(&mut self, step_id: &str, url: &str) -> Result<()> {
+            self.inner.open_url(step_id, url)
+        }
+        fn should_pause(&mut self, step_id: &str) -> Result<bool> {
+            self.inner.should_pause(step_id)
+        }
+        fn read_clipboard(&mut self) -> Option<String> {
+            self.inner.read_clipboard()
+        }
+        fn write_clipboard(&mut self, value: &str, clear_after: Option<u64>) -> bool {
+            self.inner.write_clipboard(value, clear_after)
+        }
+        fn clear_clipboard(&mut self, value: &str) -> bool {
+            self.inner.clear_clipboard(value)
+        }
+    }
+
+    #[test]
+    fn test_go_back_undoes_previous_step() {
+        let recipe = twitter();
+        let answers: Answers = serde_json::from_str(
+            r#"{ "answers": {
+                "check_access": "The developer dashboard",
+                "confirm_project": true,
+                "scope_choice": "Post tweets",
+                "collect_api_key": "abcdefghijklmnopqrstuvwxyz",
+                "collect_api_secret": "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                "collect_bearer_token": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA%3D"
+            } }"#,
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        let frontend = BackOnce {
+            inner: ScriptedFrontend::new(answers),
+            at: "collect_api_secret",
+            went_back: false,
+        };
+        let mut ctx = RunContext::builder(Box::new(frontend))
+            .output_file(env_file.to_string_lossy())
+            .build();
+
+        // Nothing to go back to yet
+        let before = ctx.snapshot(0, "collect_api_key", true);
+        assert_eq!(ctx.go_back(), None);
+
+        ctx.set_var("note".to_string(), "x".to_string());
+        ctx.set_collected("TWITTER_API_KEY".to_string(), "abc".to_string());
+        ctx.mark_completed("collect_api_key");
+        ctx.history.push(before);
+        assert_eq!(ctx.go_back(), Some(0));
+        assert!(ctx.vars.is_empty() && ctx.collected.is_empty());
+        assert!(!ctx.is_completed("collect_api_key"));
+
+        run(&recipe, &mut ctx).unwrap();
+
+        let visits = |id: &str| ctx.visited.iter().filter(|v| *v == id).count();
+        assert_eq!(visits("collect_api_key"), 2);
+        assert_eq!(visits("collect_api_secret"), 2);
+        assert_eq!(visits("scope_choice"), 1);
+        assert_eq!(ctx.collected.len(), 3);
+    }
+
+    #[test]
+    fn test_skip_step() {
+        let mut recipe = twitter();
+        for step in &mut recipe.steps {
+            if let Step::Info { id, common, .. } = step {
+                common.skippable = id == "create_project";
+            }
         }
+        let answers = r#"{ "answers": {
+            "check_access": "The developer dashboard",
+            "confirm_project": true,
+            "scope_choice": "Post tweets",
+            "collect_api_key": "abcdefghijklmnopqrstuvwxyz",
+            "collect_api_secret": "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            "collect_bearer_token": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA%3D"
+        } }"#;
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+
+        let mut ctx = scripted_ctx(answers, env_file.to_str().unwrap());
+        ctx.skip = vec!["create_project".to_string()];
+        run(&recipe, &mut ctx).unwrap();
+        assert_eq!(ctx.skipped_steps, vec!["create_project"]);
+        assert!(!ctx.is_completed("create_project"));
+        assert!(ctx.is_completed("create_app"));
+
+        let mut ctx = scripted_ctx(answers, env_file.to_str().unwrap());
+        ctx.skip = vec!["create_app".to_string()];
+        let err = run(&recipe, &mut ctx).unwrap_err();
+        assert!(matches!(err, GetapiError::NotSkippable(ref id) if id == "create_app"));
+    }
+
+    #[test]
+    fn test_checkpoint_after_each_step() {
+        let recipe = twitter();
+        let answers = r#"{ "answers": {
+            "check_access": "The developer dashboard",
+            "confirm_project": true,
+            "scope_choice": "Post tweets",
+            "collect_api_key": "abcdefghijklmnopqrstuvwxyz",
+            "collect_api_secret": "too-short"
+        } }"#;
+        let mut ctx = scripted_ctx(answers, "");
+        let saved = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let log = saved.clone();
+        ctx.checkpoint = Some(Box::new(move |ctx: &RunContext| {
+            log.borrow_mut()
+                .push((ctx.completed_steps.len(), ctx.collected.len()));
+            Ok(())
+        }));
+
+        // The run fails, but the key collected before it is already saved
+        assert!(run(&recipe, &mut ctx).is_err());
+        let saved = saved.borrow();
+        assert_eq!(saved.len(), ctx.completed_steps.len());
+        assert_eq!(saved.last(), Some(&(ctx.completed_steps.len(), 1)));
+    }
+
+    #[test]
+    fn test_failed_validation_reenters_inputs_once() {
+        let mut recipe = loader::load_all_bundled()
+            .into_iter()
+            .find(|r| r.id == "twitter")
+            .unwrap();
+        for step in &mut recipe.steps {
+            if let Step::Validate {
+                on_failure_action, ..
+            } = step
+            {
+                *on_failure_action = FailureAction::Reenter;
+            }
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        let mut ctx = scripted_ctx(
+            r#"{ "answers": {
+                "check_access": "The developer dashboard",
+                "confirm_project": true,
+                "scope_choice": "Post tweets",
+                "collect_api_key": "abcdefghijklmnopqrstuvwxyz",
+                "collect_api_secret": "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                "collect_bearer_token": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA%3D"
+            } }"#,
+            env_file.to_str().unwrap(),
+        );
+
+        // No mocked responses, so every validation fails
+        crate::http::set_mocks(Vec::new());
+        let result = run(&recipe, &mut ctx);
+        crate::http::clear_mocks();
+        result.unwrap();
+
+        let visits = |id: &str| ctx.visited.iter().filter(|v| *v == id).count();
+        assert_eq!(visits("collect_api_key"), 2);
+        assert_eq!(visits("collect_api_secret"), 2);
+        assert_eq!(visits("collect_bearer_token"), 1);
+        assert_eq!(visits("validate_creds"), 2);
+        // The second failure falls back to asking, and scripted runs save anyway
+        assert!(env_file.exists());
+    }
+
+    #[test]
+    fn test_validate_collects_missing_dependencies_first() {
+        let recipe = loader::load_all_bundled()
+            .into_iter()
+            .find(|r| r.id == "twitter")
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        let mut ctx = scripted_ctx(
+            r#"{ "answers": { "collect_api_secret": "ABCDEFGHIJKLMNOPQRSTUVWXYZ" } }"#,
+            env_file.to_str().unwrap(),
+        );
+
+        // Resumed at the validate step, with the secret never collected
+        let validate = recipe.find_step_index("validate_creds").unwrap();
+        ctx.completed_steps = recipe.steps[..validate]
+            .iter()
+            .map(|s| s.id().to_string())
+            .filter(|id| id != "collect_api_secret")
+            .collect();
+        ctx.set_collected(
+            "TWITTER_API_KEY".to_string(),
+            "abcdefghijklmnopqrstuvwxyz".to_string(),
+        );
+        ctx.current_step_index = validate;
+
+        crate::http::set_mocks(Vec::new());
+        let result = run(&recipe, &mut ctx);
+        crate::http::clear_mocks();
+        result.unwrap();
+
+        assert_eq!(
+            ctx.visited,
+            vec!["validate_creds", "collect_api_secret", "validate_creds"]
+        );
+        assert_eq!(ctx.validations.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_without_dependencies_is_skipped() {
+        let recipe = loader::load_all_bundled()
+            .into_iter()
+            .find(|r| r.id == "twitter")
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        let mut ctx = scripted_ctx(r#"{ "answers": {} }"#, env_file.to_str().unwrap());
+
+        // The secret's step was skipped, so there's nothing to go back and collect
+        let validate = recipe.find_step_index("validate_creds").unwrap();
+        ctx.completed_steps = recipe.steps[..validate]
+            .iter()
+            .map(|s| s.id().to_string())
+            .filter(|id| id != "collect_api_secret")
+            .collect();
+        ctx.skipped_steps = vec!["collect_api_secret".to_string()];
+        ctx.current_step_index = validate;
+
+        run(&recipe, &mut ctx).unwrap();
+        assert_eq!(ctx.validations.len(), 1);
+        let record = &ctx.validations[0];
+        assert!(record.skipped && !record.passed);
+        assert!(record.error.as_deref().unwrap().starts_with("missing "));
+    }
+
+    #[test]
+    fn test_invalid_input_is_not_retried() {
+        let recipe = twitter();
+        let answers = r#"{ "answers": {
+            "check_access": "The developer dashboard",
+            "confirm_project": true,
+            "scope_choice": "Post tweets",
+            "collect_api_key": "too-short"
+        } }"#;
+        let mut ctx = scripted_ctx(answers, "");
+
+        let err = run(&recipe, &mut ctx).unwrap_err();
+        assert!(matches!(err, GetapiError::InvalidAnswer(ref id, _) if id == "collect_api_key"));
+        assert!(ctx.collected.is_empty());
+    }
+
+    #[test]
+    fn test_check_saved_runs_each_check_once() {
+        let recipes = loader::load_all_bundled();
+        let github = recipes.iter().find(|r| r.id == "github").unwrap();
+        let values = HashMap::from([("GITHUB_TOKEN".to_string(), "ghp_abc".to_string())]);
+
+        crate::http::set_mocks(vec![crate::http::MockResponse {
+            method: "GET".to_string(),
+            url: "https://api.github.com/user".to_string(),
+            status: 200,
+            body: r#"{"login":"octocat"}"#.to_string(),
+        }]);
+        let checks = steps::validate::check_saved(github, &values, None).unwrap();
+        crate::http::clear_mocks();
+
+        // The fine-grained and classic branches make the same request
+        assert_eq!(checks.len(), 1);
+        match &checks[0] {
+            steps::validate::SavedCheck::Ran(record) => {
+                assert!(record.passed);
+                assert_eq!(record.identity.as_deref(), Some("octocat"));
+            }
+            other => panic!("expected a run, got {:?}", other),
+        }
+
+        let twitter = recipes.iter().find(|r| r.id == "twitter").unwrap();
+        let checks = steps::validate::check_saved(twitter, &HashMap::new(), None).unwrap();
+        assert!(matches!(
+            &checks[0],
+            steps::validate::SavedCheck::Missing { missing, .. } if missing.len() == 2
+        ));
     }
 }
@@ -0,0 +1,114 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::recipe::types::{Choice, Recipe, Step};
+
+/// Indices of the steps a run goes through from `start`, following the choices already made
+/// (step id → chosen label) and falling through to the next step otherwise. Where a choice is
+/// still to be made, the longest branch is assumed, so the counter never runs past its total.
+pub fn reachable(
+    recipe: &Recipe,
+    start: usize,
+    choices_made: &HashMap<String, String>,
+) -> Vec<usize> {
+    walk(recipe, start, choices_made, &HashSet::new())
+}
+
+fn walk(
+    recipe: &Recipe,
+    start: usize,
+    choices_made: &HashMap<String, String>,
+    seen: &HashSet<usize>,
+) -> Vec<usize> {
+    let mut seen = seen.clone();
+    let mut path = Vec::new();
+    let mut i = start;
+
+    // `seen` stops loops created by a `next` that points backwards
+    while i < recipe.steps.len() && seen.insert(i) {
+        path.push(i);
+
+        let Step::PromptChoice { id, choices, .. } = &recipe.steps[i] else {
+            i += 1;
+            continue;
+        };
+        let target = |choice: &Choice| {
+            choice
+                .next
+                .as_ref()
+                .and_then(|next| recipe.find_step_index(next))
+                .unwrap_or(i + 1)
+        };
+
+        match choices_made
+            .get(id)
+            .and_then(|label| choices.iter().find(|c| &c.label == label))
+        {
+            Some(choice) => i = target(choice),
+            None => {
+                let longest = choices
+                    .iter()
+                    .map(|choice| walk(recipe, target(choice), choices_made, &seen))
+                    .max_by_key(|branch| branch.len())
+                    .unwrap_or_default();
+                path.extend(longest);
+                break;
+            }
+        }
+    }
+
+    path
+}
+
+/// Total `estimated_seconds` of the given steps, or `None` if none of them has an estimate.
+pub fn estimated_seconds(recipe: &Recipe, steps: &[usize]) -> Option<u64> {
+    steps
+        .iter()
+        .filter_map(|&i| recipe.steps[i].estimated_seconds())
+        .reduce(|a, b| a + b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe() -> Recipe {
+        serde_json::from_str(
+            r#"{
+                "schema_version": "1",
+                "id": "demo",
+                "display_name": "Demo",
+                "description": "",
+                "outputs": [],
+                "steps": [
+                    { "id": "start", "type": "info", "message": "", "estimated_seconds": 10 },
+                    { "id": "pick", "type": "prompt_choice", "message": "", "choices": [
+                        { "label": "Short", "next": "done" },
+                        { "label": "Long" }
+                    ] },
+                    { "id": "long_1", "type": "info", "message": "", "estimated_seconds": 60 },
+                    { "id": "long_2", "type": "info", "message": "" },
+                    { "id": "done", "type": "info", "message": "", "estimated_seconds": 5 }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reachable_follows_choices() {
+        let recipe = recipe();
+        let mut choices = HashMap::new();
+        assert_eq!(reachable(&recipe, 0, &choices), vec![0, 1, 2, 3, 4]);
+
+        choices.insert("pick".to_string(), "Short".to_string());
+        assert_eq!(reachable(&recipe, 0, &choices), vec![0, 1, 4]);
+        assert_eq!(reachable(&recipe, 4, &choices), vec![4]);
+    }
+
+    #[test]
+    fn test_estimated_seconds() {
+        let recipe = recipe();
+        assert_eq!(estimated_seconds(&recipe, &[0, 1, 4]), Some(15));
+        assert_eq!(estimated_seconds(&recipe, &[1, 3]), None);
+    }
+}
@@ -1,28 +1,159 @@
+use std::collections::HashMap;
+
+use crate::clipboard;
 use crate::error::Result;
+use crate::output::OutputFormat;
 use crate::recipe::template;
 use crate::runner::context::RunContext;
-use crate::ui;
+use crate::secrets;
 
-pub fn handle(value: &str, message: &str, ctx: &RunContext) -> Result<()> {
+/// Copy `value` to the clipboard. Besides template variables, it may use collected outputs,
+/// e.g. `{{MY_TOKEN}}`.
+pub fn handle(
+    value: &str,
+    message: &str,
+    sensitive: bool,
+    clear_after: Option<u64>,
+    ctx: &mut RunContext,
+) -> Result<()> {
     let expanded_msg = template::expand(message, &ctx.vars)?;
-    let expanded_val = template::expand(value, &ctx.vars)?;
-
-    ui::print_info(&expanded_msg);
+    ctx.frontend.info(&expanded_msg);
 
     if ctx.non_interactive {
-        ui::print_info(&format!("  Value: {}", expanded_val));
+        // Placeholders only: collected credentials aren't printed in a checklist
+        ctx.frontend.info(&format!(
+            "  Value: {}",
+            template::expand_lenient(value, &ctx.vars)
+        ));
         return Ok(());
     }
 
-    match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&expanded_val)) {
-        Ok(()) => {
-            ui::print_success("Copied to clipboard.");
-        }
-        Err(_) => {
-            ui::print_warning("Could not copy to clipboard. Here's the value:");
-            ui::print_info(&format!("  {}", expanded_val));
+    let vars: HashMap<String, String> = ctx
+        .collected
+        .iter()
+        .chain(&ctx.vars)
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let expanded_val = template::expand(value, &vars)?;
+
+    let clear_after = match clear_after {
+        Some(0) => None,
+        Some(secs) => Some(secs),
+        None if sensitive => Some(clipboard::DEFAULT_CLEAR_AFTER_SECS),
+        None => None,
+    };
+
+    if ctx.frontend.write_clipboard(&expanded_val, clear_after) {
+        ctx.frontend.success("Copied to clipboard.");
+        if let Some(secs) = clear_after {
+            ctx.frontend.info(&format!(
+                "It will be cleared from your clipboard in {} seconds.",
+                secs
+            ));
         }
+    } else if sensitive {
+        // Never print a secret in full: it would stay in the scrollback or a client transcript
+        ctx.frontend.warning(&format!(
+            "Could not copy to clipboard. The value ({}) is built from your credentials.",
+            secrets::mask(&expanded_val)
+        ));
+        let saved = match ctx.output_format {
+            OutputFormat::Stdout => "They're printed when setup finishes.".to_string(),
+            _ => format!("They're saved to {} when setup finishes.", ctx.output_file),
+        };
+        ctx.frontend.info(&saved);
+    } else {
+        ctx.frontend
+            .warning("Could not copy to clipboard. Here's the value:");
+        ctx.frontend.info(&format!("  {}", expanded_val));
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::answers::Answers;
+    use crate::frontend::scripted::ScriptedFrontend;
+    use crate::frontend::{Frontend, Message};
+
+    /// A scripted frontend without a working clipboard, keeping what it was asked to print.
+    struct NoClipboard {
+        inner: ScriptedFrontend,
+        printed: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+    }
+
+    impl Frontend for NoClipboard {
+        fn print(&mut self, message: Message) {
+            self.printed.borrow_mut().push(format!("{:?}", message));
+        }
+        fn confirm(&mut self, step_id: &str, prompt: &str, default: bool) -> Result<bool> {
+            self.inner.confirm(step_id, prompt, default)
+        }
+        fn select(&mut self, id: &str, prompt: &str, items: &[&str], d: usize) -> Result<usize> {
+            self.inner.select(id, prompt, items, d)
+        }
+        fn input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+            self.inner.input(step_id, prompt)
+        }
+        fn secret_input(&mut self, step_id: &str, prompt: &str) -> Result<String> {
+            self.inner.secret_input(step_id, prompt)
+        }
+        fn reject_input(&mut self, step_id: &str, reason: &str) -> Result<()> {
+            self.inner.reject_input(step_id, reason)
+        }
+        fn open_url(&mut self, step_id: &str, url: &str) -> Result<()> {
+            self.inner.open_url(step_id, url)
+        }
+        fn should_pause(&mut self, step_id: &str) -> Result<bool> {
+            self.inner.should_pause(step_id)
+        }
+        fn read_clipboard(&mut self) -> Option<String> {
+            None
+        }
+        fn write_clipboard(&mut self, _value: &str, _clear_after: Option<u64>) -> bool {
+            false
+        }
+        fn clear_clipboard(&mut self, _value: &str) -> bool {
+            false
+        }
+    }
+
+    fn failed_copy(sensitive: bool) -> String {
+        let printed = std::rc::Rc::default();
+        let frontend = NoClipboard {
+            inner: ScriptedFrontend::new(Answers::default()),
+            printed: std::rc::Rc::clone(&printed),
+        };
+        let mut ctx = RunContext::builder(Box::new(frontend))
+            .output_file("creds.env")
+            .build();
+        ctx.set_collected("MY_TOKEN".to_string(), "tok-0123456789abcdef".to_string());
+        handle(
+            "Bearer {{MY_TOKEN}}",
+            "Copy the header",
+            sensitive,
+            None,
+            &mut ctx,
+        )
+        .unwrap();
+        let lines = printed.borrow().join("\n");
+        lines
+    }
+
+    #[test]
+    fn test_failed_copy_masks_sensitive_value() {
+        let printed = failed_copy(true);
+        assert!(!printed.contains("0123456789abcdef"), "{}", printed);
+        assert!(printed.contains("Bea...cdef"), "{}", printed);
+        assert!(printed.contains("creds.env"), "{}", printed);
+
+        let printed = failed_copy(false);
+        assert!(
+            printed.contains("Bearer tok-0123456789abcdef"),
+            "{}",
+            printed
+        );
+    }
+}
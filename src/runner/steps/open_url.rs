@@ -1,11 +1,9 @@
-use dialoguer::Confirm;
-
-use crate::error::{GetapiError, Result};
+use crate::error::Result;
+use crate::frontend::Message;
 use crate::recipe::template;
 use crate::runner::context::RunContext;
-use crate::ui;
 
-pub fn handle(url: &str, message: &str, ctx: &RunContext) -> Result<()> {
+pub fn handle(step_id: &str, url: &str, message: &str, ctx: &mut RunContext) -> Result<()> {
     let expanded_msg = if ctx.non_interactive {
         template::expand_lenient(message, &ctx.vars)
     } else {
@@ -17,24 +15,11 @@ pub fn handle(url: &str, message: &str, ctx: &RunContext) -> Result<()> {
         template::expand(url, &ctx.vars)?
     };
 
-    ui::print_info(&expanded_msg);
-    ui::print_url(&expanded_url);
+    ctx.frontend.info(&expanded_msg);
+    ctx.frontend.print(Message::Url(&expanded_url));
 
     if !ctx.non_interactive {
-        let open_browser = Confirm::new()
-            .with_prompt("Open in browser?")
-            .default(true)
-            .interact()
-            .map_err(|_| GetapiError::UserCancelled)?;
-
-        if open_browser {
-            if let Err(e) = open::that(&expanded_url) {
-                ui::print_warning(&format!(
-                    "Could not open browser automatically: {}. Open the URL above manually.",
-                    e
-                ));
-            }
-        }
+        ctx.frontend.open_url(step_id, &expanded_url)?;
     }
     Ok(())
 }
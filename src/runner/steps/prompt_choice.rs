@@ -1,10 +1,8 @@
-use dialoguer::Select;
-
-use crate::error::{GetapiError, Result};
+use crate::error::Result;
+use crate::frontend::Message;
 use crate::recipe::template;
 use crate::recipe::types::Choice;
 use crate::runner::context::RunContext;
-use crate::ui;
 
 pub fn handle(
     step_id: &str,
@@ -15,21 +13,16 @@ pub fn handle(
     let expanded = template::expand(message, &ctx.vars)?;
 
     if ctx.non_interactive {
-        ui::print_info(&expanded);
+        ctx.frontend.info(&expanded);
         for choice in choices.iter() {
-            println!("    {} {}", console::style("·").dim(), choice.label);
+            ctx.frontend.print(Message::Bullet(&choice.label));
         }
         return Ok(());
     }
 
     let labels: Vec<&str> = choices.iter().map(|c| c.label.as_str()).collect();
 
-    let selection = Select::new()
-        .with_prompt(&expanded)
-        .items(&labels)
-        .default(0)
-        .interact()
-        .map_err(|_| GetapiError::UserCancelled)?;
+    let selection = ctx.frontend.select(step_id, &expanded, &labels, 0)?;
 
     let chosen = &choices[selection];
 
@@ -1,27 +1,22 @@
-use dialoguer::Confirm;
-
 use crate::error::{GetapiError, Result};
+use crate::frontend::Message;
 use crate::recipe::template;
 use crate::runner::context::RunContext;
-use crate::ui;
 
-pub fn handle(message: &str, ctx: &RunContext) -> Result<()> {
+pub fn handle(step_id: &str, message: &str, ctx: &mut RunContext) -> Result<()> {
     let expanded = template::expand(message, &ctx.vars)?;
 
     if ctx.non_interactive {
-        println!("  {} {}", console::style("→").cyan(), expanded);
+        ctx.frontend.print(Message::Action(&expanded));
         return Ok(());
     }
 
-    let confirmed = Confirm::new()
-        .with_prompt(&expanded)
-        .default(true)
-        .interact()
-        .map_err(|_| GetapiError::UserCancelled)?;
+    let confirmed = ctx.frontend.confirm(step_id, &expanded, true)?;
 
     if !confirmed {
-        ui::print_warning("Take your time — you can resume later with `getapi resume`.");
-        return Err(GetapiError::UserCancelled);
+        ctx.frontend
+            .warning("Take your time — you can resume later with `getapi resume`.");
+        return Err(GetapiError::Paused);
     }
 
     Ok(())
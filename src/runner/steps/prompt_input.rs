@@ -1,64 +1,175 @@
-use dialoguer::Input;
-use regex::Regex;
-
-use crate::error::{GetapiError, Result};
+use crate::error::Result;
+use crate::frontend::{Frontend, Message};
+use crate::recipe::patterns;
 use crate::recipe::template;
+use crate::recipe::types::Transform;
 use crate::runner::context::RunContext;
-use crate::ui;
+use crate::runner::transform;
+use crate::secrets;
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle(
+    step_id: &str,
     message: &str,
     output_key: &str,
     validation: Option<&str>,
     validation_error: Option<&str>,
+    transforms: &[Transform],
+    sensitive: bool,
     ctx: &mut RunContext,
 ) -> Result<()> {
     let expanded = template::expand(message, &ctx.vars)?;
+    let pattern = validation.map(patterns::compile).transpose()?;
+
+    if ctx.use_env {
+        if let Some(value) = from_env(
+            output_key,
+            pattern.as_ref(),
+            validation_error,
+            transforms,
+            ctx,
+        )? {
+            ctx.frontend
+                .success(&format!("{} taken from environment.", output_key));
+            collect(output_key, value, sensitive, ctx);
+            return Ok(());
+        }
+    }
 
     if ctx.non_interactive {
-        println!(
-            "  {} {} {} ${}",
-            console::style("→").cyan(),
-            expanded,
-            console::style("→").dim(),
-            output_key
-        );
+        ctx.frontend
+            .print(Message::Action(&format!("{} → ${}", expanded, output_key)));
         return Ok(());
     }
 
-    let validation_regex = match validation {
-        Some(pattern) => Some(
-            Regex::new(pattern)
-                .map_err(|e| GetapiError::InvalidRecipe(format!("Bad validation regex: {}", e)))?,
-        ),
-        None => None,
-    };
+    if let Some(ref pattern) = pattern {
+        if let Some(value) = offer_clipboard(
+            step_id,
+            &expanded,
+            pattern,
+            transforms,
+            ctx.frontend.as_mut(),
+        )? {
+            collect(output_key, value, sensitive, ctx);
+            return Ok(());
+        }
+    }
 
     loop {
-        let value: String = Input::new()
-            .with_prompt(&expanded)
-            .interact_text()
-            .map_err(|_| GetapiError::UserCancelled)?;
+        let value = if sensitive {
+            ctx.frontend.secret_input(step_id, &expanded)?
+        } else {
+            ctx.frontend.input(step_id, &expanded)?
+        };
 
-        let value = value.trim().to_string();
+        let pasted = value.trim();
+        let value = transform::apply(transforms, pasted);
 
         if value.is_empty() {
-            ui::print_warning("Value cannot be empty. Please try again.");
+            ctx.frontend
+                .reject_input(step_id, "Value cannot be empty. Please try again.")?;
             continue;
         }
 
-        if let Some(ref re) = validation_regex {
-            if !re.is_match(&value) {
-                let msg = validation_error
-                    .unwrap_or("Input doesn't match the expected format. Please try again.");
-                ui::print_warning(msg);
+        if value != pasted {
+            let shown = if sensitive {
+                secrets::mask(&value)
+            } else {
+                value.clone()
+            };
+            ctx.frontend.info(&format!("Using: {}", shown));
+        }
+
+        if let Some(ref pattern) = pattern {
+            if !pattern.is_match(&value) {
+                let msg = validation_error.unwrap_or(&pattern.default_error);
+                ctx.frontend.reject_input(step_id, msg)?;
                 continue;
             }
         }
 
-        ctx.set_collected(output_key.to_string(), value);
+        collect(output_key, value, sensitive, ctx);
         break;
     }
 
     Ok(())
 }
+
+fn collect(output_key: &str, value: String, sensitive: bool, ctx: &mut RunContext) {
+    if sensitive {
+        ctx.set_secret(output_key.to_string(), value);
+    } else {
+        ctx.set_collected(output_key.to_string(), value);
+    }
+}
+
+/// Look up `output_key` in the process environment. A value that fails the step's validation
+/// is reported and ignored, so the user is prompted as usual.
+fn from_env(
+    output_key: &str,
+    pattern: Option<&patterns::Pattern>,
+    validation_error: Option<&str>,
+    transforms: &[Transform],
+    ctx: &mut RunContext,
+) -> Result<Option<String>> {
+    let Some(raw) = std::env::var(output_key)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+    else {
+        return Ok(None);
+    };
+    let value = transform::apply(transforms, raw.trim());
+
+    if let Some(pattern) = pattern {
+        if !pattern.is_match(&value) {
+            let msg = validation_error.unwrap_or(&pattern.default_error);
+            ctx.frontend.warning(&format!(
+                "Ignoring {} from the environment: {}",
+                output_key, msg
+            ));
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(value))
+}
+
+/// If the clipboard already holds something matching the step's validation, offer to use it
+/// instead of asking the user to paste.
+fn offer_clipboard(
+    step_id: &str,
+    prompt: &str,
+    pattern: &patterns::Pattern,
+    transforms: &[Transform],
+    frontend: &mut dyn Frontend,
+) -> Result<Option<String>> {
+    let Some(raw) = frontend.read_clipboard() else {
+        return Ok(None);
+    };
+    let value = transform::apply(transforms, raw.trim());
+    if value.is_empty() || !pattern.is_match(&value) {
+        return Ok(None);
+    }
+
+    frontend.info(prompt);
+    let use_it = frontend.confirm(
+        step_id,
+        &format!(
+            "Use the value from your clipboard ({})?",
+            secrets::mask(&value)
+        ),
+        true,
+    )?;
+
+    if !use_it {
+        return Ok(None);
+    }
+
+    let clear = frontend.confirm(step_id, "Clear it from your clipboard?", true)?;
+
+    if clear && frontend.clear_clipboard(&raw) {
+        frontend.success("Clipboard cleared.");
+    }
+
+    Ok(Some(value))
+}
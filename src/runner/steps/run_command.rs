@@ -1,29 +1,27 @@
-use std::process::Command;
-
 use crate::error::Result;
+use crate::frontend::Message;
 use crate::recipe::template;
 use crate::runner::context::RunContext;
-use crate::ui;
 
-pub fn handle(command: &str, message: &str, ctx: &RunContext) -> Result<()> {
+pub fn handle(step_id: &str, command: &str, message: &str, ctx: &mut RunContext) -> Result<()> {
     let expanded_msg = template::expand(message, &ctx.vars)?;
     let expanded_cmd = template::expand(command, &ctx.vars)?;
 
     if ctx.non_interactive {
-        println!("  {} {}", console::style("→").cyan(), expanded_msg);
-        ui::print_command(&expanded_cmd);
+        ctx.frontend.print(Message::Action(&expanded_msg));
+        ctx.frontend.print(Message::Command(&expanded_cmd));
         return Ok(());
     }
 
-    ui::print_info(&expanded_msg);
-    ui::print_command(&expanded_cmd);
+    ctx.frontend.info(&expanded_msg);
+    ctx.frontend.print(Message::Command(&expanded_cmd));
 
-    let output = Command::new("sh").arg("-c").arg(&expanded_cmd).status()?;
+    let output = ctx.frontend.run_command(step_id, &expanded_cmd)?;
 
     if output.success() {
-        ui::print_success("Command completed successfully.");
+        ctx.frontend.success("Command completed successfully.");
     } else {
-        ui::print_warning(&format!(
+        ctx.frontend.warning(&format!(
             "Command exited with status {}. You may need to run it manually.",
             output.code().unwrap_or(-1)
         ));
@@ -1,40 +1,375 @@
 use std::collections::HashMap;
+use std::time::Instant;
 
-use crate::error::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::error::{GetapiError, Result};
+use crate::frontend::Message;
+use crate::http;
+use crate::recipe::deps;
+use crate::recipe::types::{FailureAction, Recipe, Step};
 use crate::runner::context::RunContext;
-use crate::ui;
-use crate::validators;
+use crate::secrets;
+use crate::session::state::{self, KeyStates};
+use crate::validators::{self, ValidationRecord};
+
+pub fn handle(recipe: &Recipe, step: &Step, ctx: &mut RunContext) -> Result<()> {
+    let Step::Validate {
+        id,
+        method,
+        message,
+        depends_on,
+        on_success,
+        on_failure,
+        on_failure_action,
+        config,
+        ..
+    } = step
+    else {
+        return Ok(());
+    };
+
+    if ctx.non_interactive {
+        ctx.frontend.print(Message::Action(&format!(
+            "Validate credentials using: {}",
+            method
+        )));
+        return Ok(());
+    }
+
+    if !dependencies_met(recipe, id, method, depends_on, ctx) {
+        // A missing dependency with nowhere to go back to collect it is a skipped validation
+        if ctx.strict && ctx.jump_to.is_none() {
+            let error = ctx.validations.last().and_then(|v| v.error.clone());
+            return Err(GetapiError::MissingCredentials(error.unwrap_or_default()));
+        }
+        return Ok(());
+    }
+
+    register_secrets(recipe, &ctx.collected);
+
+    let reenter: Vec<String> = deps::input_steps(recipe, depends_on)
+        .iter()
+        .map(|s| s.id().to_string())
+        .collect();
+    // The recipe's action applies to the first failure; after that the user decides
+    let mut action = *on_failure_action;
+    if ctx
+        .validations
+        .iter()
+        .any(|v| &v.step_id == id && !v.passed && !v.skipped)
+    {
+        action = FailureAction::Ask;
+    }
+
+    loop {
+        let passed = check(
+            id,
+            method,
+            message,
+            on_success.as_deref(),
+            on_failure.as_deref(),
+            config,
+            ctx,
+        )?;
+        if passed {
+            return Ok(());
+        }
+
+        if action == FailureAction::Ask || (action == FailureAction::Reenter && reenter.is_empty())
+        {
+            action = ctx
+                .frontend
+                .validation_failed(id, !reenter.is_empty(), !ctx.strict)?;
+        }
+        if ctx.strict && action == FailureAction::SaveAnyway {
+            action = FailureAction::Abort;
+        }
+
+        match std::mem::replace(&mut action, FailureAction::Ask) {
+            FailureAction::Retry => continue,
+            FailureAction::Reenter if !reenter.is_empty() => {
+                ctx.jump_to = Some(reenter[0].clone());
+                ctx.revisit = reenter;
+                ctx.revisit.push(id.clone());
+                return Ok(());
+            }
+            FailureAction::Abort => {
+                let network_error = ctx.validations.last().is_some_and(|v| v.network_error);
+                let message = "stopped without saving credentials.".to_string();
+                return Err(if network_error {
+                    GetapiError::Network(message)
+                } else {
+                    GetapiError::ValidationFailed(message)
+                });
+            }
+            _ => {
+                ctx.frontend
+                    .warning("Saving the credentials without validating them.");
+                return Ok(());
+            }
+        }
+    }
+}
 
-pub fn handle(
+/// Run the validator and report the outcome, without asking what to do about a failure.
+/// Returns whether it passed.
+pub fn check(
+    step_id: &str,
     method: &str,
     message: &str,
     on_success: Option<&str>,
     on_failure: Option<&str>,
     config: &HashMap<String, String>,
-    ctx: &RunContext,
-) -> Result<()> {
-    if ctx.non_interactive {
-        println!(
-            "  {} Validate credentials using: {}",
-            console::style("→").cyan(),
-            method
-        );
-        return Ok(());
+    ctx: &mut RunContext,
+) -> Result<bool> {
+    ctx.frontend.info(message);
+
+    let record = run(step_id, method, config, &ctx.collected)?;
+    if record.passed {
+        let msg = on_success.unwrap_or("Credentials validated successfully.");
+        match record.identity {
+            Some(ref identity) => ctx.frontend.success(&format!("{} ({})", msg, identity)),
+            None => ctx.frontend.success(msg),
+        }
+    } else {
+        let msg = on_failure.unwrap_or("Validation failed.");
+        let error = record.error.as_deref().unwrap_or_default();
+        ctx.frontend.warning(&format!("{} ({})", msg, error));
     }
 
-    ui::print_info(message);
+    let passed = record.passed;
+    ctx.frontend.validation_result(&record);
+    ctx.validations.push(record);
+    Ok(passed)
+}
 
-    match validators::run(method, &ctx.collected, config) {
-        Ok(()) => {
-            let msg = on_success.unwrap_or("Credentials validated successfully.");
-            ui::print_success(msg);
-            Ok(())
-        }
+/// Run the validator quietly, timing it. Fails only if a replayed response is missing, which
+/// says nothing about the credentials.
+pub fn run(
+    step_id: &str,
+    method: &str,
+    config: &HashMap<String, String>,
+    values: &HashMap<String, String>,
+) -> Result<ValidationRecord> {
+    let started = Instant::now();
+    let result = validators::run(method, values, config);
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    if let Err(GetapiError::Cassette(e)) = result {
+        return Err(GetapiError::Cassette(e));
+    }
+
+    let network_error = matches!(
+        result,
+        Err(GetapiError::Http(_)) | Err(GetapiError::Network(_))
+    );
+    let (passed, identity, error) = match result {
+        Ok(identity) => (true, identity, None),
+        // Transport errors quote the URL, which can carry a token
         Err(e) => {
-            let msg = on_failure.unwrap_or("Validation failed.");
-            ui::print_warning(&format!("{} ({})", msg, e));
-            // Validation failure is a warning, not a fatal error
-            Ok(())
+            let secrets: Vec<&String> = values.values().collect();
+            (false, None, Some(secrets::scrub(&e.to_string(), &secrets)))
+        }
+    };
+    Ok(ValidationRecord {
+        step_id: step_id.to_string(),
+        method: method.to_string(),
+        passed,
+        error,
+        identity,
+        elapsed_ms,
+        network_error,
+        skipped: false,
+    })
+}
+
+/// A validate step's outcome when checking saved credentials outside a walkthrough.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SavedCheck {
+    Ran(ValidationRecord),
+    /// Not run because these `depends_on` values aren't saved.
+    Missing {
+        step_id: String,
+        method: String,
+        missing: Vec<String>,
+    },
+    /// Not run because the same values passed recently (`--max-age`).
+    Recent {
+        step_id: String,
+        method: String,
+        validated_at: DateTime<Utc>,
+    },
+}
+
+impl SavedCheck {
+    pub fn passed(&self) -> bool {
+        match self {
+            SavedCheck::Ran(record) => record.passed,
+            SavedCheck::Missing { .. } => false,
+            SavedCheck::Recent { .. } => true,
         }
     }
 }
+
+/// Run every validate step in `recipe` against saved `values`, skipping steps whose
+/// dependencies aren't saved. Steps making the same check (e.g. one per branch) run once.
+/// With `skip_recent`, steps whose values passed within the given age aren't run again.
+pub fn check_saved(
+    recipe: &Recipe,
+    values: &HashMap<String, String>,
+    skip_recent: Option<(&KeyStates, Duration)>,
+) -> Result<Vec<SavedCheck>> {
+    register_secrets(recipe, values);
+
+    let mut seen: Vec<(&str, &HashMap<String, String>)> = Vec::new();
+    let mut checks = Vec::new();
+    for step in &recipe.steps {
+        let Step::Validate {
+            id,
+            method,
+            depends_on,
+            config,
+            ..
+        } = step
+        else {
+            continue;
+        };
+        if seen.contains(&(method.as_str(), config)) {
+            continue;
+        }
+        seen.push((method.as_str(), config));
+
+        let missing = deps::missing_values(recipe, depends_on, values);
+        let keys = deps::value_keys(recipe, depends_on);
+        let recent = skip_recent
+            .and_then(|(entries, max_age)| state::recent_success(entries, &keys, values, max_age));
+        if let Some(validated_at) = recent {
+            checks.push(SavedCheck::Recent {
+                step_id: id.clone(),
+                method: method.clone(),
+                validated_at,
+            });
+        } else if missing.is_empty() {
+            checks.push(SavedCheck::Ran(run(id, method, config, values)?));
+        } else {
+            checks.push(SavedCheck::Missing {
+                step_id: id.clone(),
+                method: method.clone(),
+                missing,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+/// Keep every sensitive value out of the HTTP log, not just the ones a validator sends.
+fn register_secrets(recipe: &Recipe, values: &HashMap<String, String>) {
+    http::log::add_secrets(
+        values
+            .iter()
+            .filter(|(key, _)| recipe.is_sensitive(key))
+            .map(|(_, value)| value.clone()),
+    );
+}
+
+/// Whether everything in `depends_on` has been collected or completed. If not, inputs that
+/// haven't been asked for yet are collected first, and anything else is reported and the
+/// validation skipped.
+fn dependencies_met(
+    recipe: &Recipe,
+    step_id: &str,
+    method: &str,
+    depends_on: &[String],
+    ctx: &mut RunContext,
+) -> bool {
+    let missing: Vec<String> = depends_on
+        .iter()
+        .filter(|d| !is_met(recipe, d, ctx))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return true;
+    }
+
+    // Go back for inputs that come earlier in the recipe but haven't run, e.g. after a resume
+    let position = recipe.find_step_index(step_id);
+    let inputs: Vec<String> = deps::input_steps(recipe, &missing)
+        .iter()
+        .filter(|s| recipe.find_step_index(s.id()) < position && !ctx.is_skipped(s.id()))
+        .map(|s| s.id().to_string())
+        .collect();
+    // Several entries (a step id and its output key, say) can name the same input
+    let all_inputs = missing.iter().all(|d| {
+        deps::resolve(recipe, d).is_some_and(|step| inputs.iter().any(|i| i == step.id()))
+    });
+    if all_inputs {
+        ctx.frontend.info(&format!(
+            "Collecting {} before validating.",
+            inputs.join(", ")
+        ));
+        ctx.jump_to = Some(inputs[0].clone());
+        ctx.revisit = inputs;
+        ctx.revisit.push(step_id.to_string());
+        return false;
+    }
+
+    let error = format!("missing {}", missing.join(", "));
+    ctx.frontend.warning(&format!(
+        "Can't validate yet: {}. Run `getapi validate {}` once they're set.",
+        error, recipe.id
+    ));
+    let record = ValidationRecord {
+        step_id: step_id.to_string(),
+        method: method.to_string(),
+        error: Some(error),
+        skipped: true,
+        ..Default::default()
+    };
+    ctx.frontend.validation_result(&record);
+    ctx.validations.push(record);
+    false
+}
+
+/// A `depends_on` entry is met once its value is collected (for inputs and output keys) or its
+/// step has completed.
+fn is_met(recipe: &Recipe, dependency: &str, ctx: &RunContext) -> bool {
+    match deps::resolve(recipe, dependency) {
+        Some(Step::PromptInput { output_key, .. }) => ctx.collected.contains_key(output_key),
+        Some(step) => ctx.is_completed(step.id()),
+        None => ctx.collected.contains_key(dependency),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::answers::Answers;
+    use crate::frontend::scripted::ScriptedFrontend;
+    use crate::recipe::loader;
+
+    #[test]
+    fn test_goes_back_for_input_named_twice() {
+        let recipe = loader::load_all_bundled()
+            .into_iter()
+            .find(|r| r.id == "twitter")
+            .unwrap();
+        let validate = recipe.find_step_index("validate_creds").unwrap();
+        let mut ctx =
+            RunContext::builder(Box::new(ScriptedFrontend::new(Answers::default()))).build();
+        ctx.completed_steps = recipe.steps[..validate]
+            .iter()
+            .map(|s| s.id().to_string())
+            .filter(|id| id != "collect_api_key")
+            .collect();
+
+        // The step and its output key both resolve to collect_api_key
+        let depends_on = ["collect_api_key".to_string(), "TWITTER_API_KEY".to_string()];
+        let met = dependencies_met(&recipe, "validate_creds", "http_get", &depends_on, &mut ctx);
+        assert!(!met);
+        assert!(ctx.validations.is_empty());
+        assert_eq!(ctx.jump_to.as_deref(), Some("collect_api_key"));
+        assert_eq!(ctx.revisit, vec!["collect_api_key", "validate_creds"]);
+    }
+}
@@ -1,15 +1,20 @@
 use crate::error::Result;
+use crate::frontend::Message;
 use crate::runner::context::RunContext;
-use crate::ui;
 
-pub fn handle(message: &str, resume_hint: Option<&str>, ctx: &mut RunContext) -> Result<()> {
-    ui::print_pause(message);
+pub fn handle(
+    step_id: &str,
+    message: &str,
+    resume_hint: Option<&str>,
+    ctx: &mut RunContext,
+) -> Result<()> {
+    ctx.frontend.print(Message::Pause(message));
 
     if let Some(hint) = resume_hint {
-        ui::print_info(hint);
+        ctx.frontend.info(hint);
     }
 
-    if !ctx.non_interactive {
+    if !ctx.non_interactive && ctx.frontend.should_pause(step_id)? {
         ctx.paused = true;
     }
 
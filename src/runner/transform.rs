@@ -0,0 +1,118 @@
+use crate::recipe::types::Transform;
+
+/// Run a `prompt_input` value through the step's `transform` pipeline, in order.
+pub fn apply(transforms: &[Transform], value: &str) -> String {
+    let mut value = value.to_string();
+    for transform in transforms {
+        value = apply_one(transform, &value);
+    }
+    value
+}
+
+fn apply_one(transform: &Transform, value: &str) -> String {
+    match transform {
+        Transform::Trim => value.trim().to_string(),
+        Transform::StripPrefix(prefix) => strip_prefix_ignore_case(value, prefix).to_string(),
+        Transform::StripQuotes => strip_quotes(value).to_string(),
+        Transform::RegexExtract(pattern) => match pattern.regex().captures(value) {
+            Some(caps) => caps
+                .get(1)
+                .or_else(|| caps.get(0))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default(),
+            None => value.to_string(),
+        },
+        Transform::Lowercase => value.to_lowercase(),
+        Transform::UrlPathSegment => url_path_segment(value).to_string(),
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> &'a str {
+    match value.get(..prefix.len()) {
+        Some(head) if head.eq_ignore_ascii_case(prefix) => &value[prefix.len()..],
+        _ => value,
+    }
+}
+
+fn strip_quotes(value: &str) -> &str {
+    for quote in ['"', '\'', '`'] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+/// Returns the last non-empty path segment of an http(s) URL, ignoring query and fragment.
+/// Values that aren't URLs are returned unchanged.
+fn url_path_segment(value: &str) -> &str {
+    let Some(rest) = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+    else {
+        return value;
+    };
+
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let path = rest
+        .split_once('/')
+        .map(|(_, path)| path)
+        .unwrap_or_default();
+
+    path.rsplit('/').find(|s| !s.is_empty()).unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recipe::types::ExtractPattern;
+
+    #[test]
+    fn test_strip_prefix_and_quotes() {
+        let transforms = vec![
+            Transform::StripQuotes,
+            Transform::StripPrefix("Bearer ".to_string()),
+            Transform::Trim,
+        ];
+        assert_eq!(apply(&transforms, "\"bearer  abc123\""), "abc123");
+        assert_eq!(apply(&transforms, "abc123"), "abc123");
+    }
+
+    #[test]
+    fn test_url_path_segment() {
+        let transforms = vec![Transform::UrlPathSegment];
+        assert_eq!(
+            apply(
+                &transforms,
+                "https://www.notion.so/acme/0123456789abcdef0123456789abcdef?v=42"
+            ),
+            "0123456789abcdef0123456789abcdef"
+        );
+        assert_eq!(apply(&transforms, "not-a-url"), "not-a-url");
+        assert_eq!(
+            apply(&transforms, "https://example.com"),
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn test_regex_extract() {
+        let transforms = vec![Transform::RegexExtract(
+            ExtractPattern::new("([0-9a-f]{32})$").unwrap(),
+        )];
+        assert_eq!(
+            apply(&transforms, "My-Database-0123456789abcdef0123456789abcdef"),
+            "0123456789abcdef0123456789abcdef"
+        );
+        assert_eq!(apply(&transforms, "short"), "short");
+    }
+
+    #[test]
+    fn test_bad_regex_rejected_at_load() {
+        let bad: Result<Vec<Transform>, _> = serde_json::from_str(r#"[{ "regex_extract": "(" }]"#);
+        assert!(bad
+            .unwrap_err()
+            .to_string()
+            .contains("bad regex_extract pattern"));
+    }
+}
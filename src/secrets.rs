@@ -0,0 +1,84 @@
+use sha2::{Digest, Sha256};
+
+/// Mask a secret for display, keeping just enough to recognise it (e.g. `sk-...abcd`).
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < 12 {
+        return "*".repeat(chars.len().max(4));
+    }
+    let head: String = chars[..3].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}...{}", head, tail)
+}
+
+/// A short, non-reversible fingerprint of a secret (hex SHA-256 prefix), safe to store or pass
+/// between processes in place of the value itself.
+pub fn fingerprint(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    digest[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Placeholder written in place of secret values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Secrets shorter than this are left in place by `scrub`. No provider issues credentials this
+/// short, and replacing every occurrence of a two- or three-character value (a region code, a
+/// `yes`) would mangle unrelated text without protecting anything.
+pub const MIN_SCRUB_LEN: usize = 4;
+
+/// Replace every occurrence of any of `secrets` in `text` with a placeholder. Values shorter
+/// than `MIN_SCRUB_LEN` are left alone.
+pub fn scrub<S: AsRef<str>>(text: &str, secrets: &[S]) -> String {
+    let mut secrets: Vec<&str> = secrets
+        .iter()
+        .map(|s| s.as_ref())
+        .filter(|s| s.chars().count() >= MIN_SCRUB_LEN)
+        .collect();
+    // Longest first, so a secret containing another is replaced whole
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+
+    let mut result = text.to_string();
+    for secret in secrets {
+        result = result.replace(secret, REDACTED);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("sk-0123456789abcd"), "sk-...abcd");
+        assert_eq!(mask("short"), "*****");
+        assert_eq!(mask(""), "****");
+    }
+
+    #[test]
+    fn test_scrub() {
+        let secrets = ["sk-0123456789", "abc"];
+        assert_eq!(
+            scrub("Bearer sk-0123456789 abc", &secrets),
+            "Bearer [REDACTED] abc"
+        );
+    }
+
+    #[test]
+    fn test_scrub_short_secrets() {
+        // Short PINs and passcodes are still scrubbed...
+        assert_eq!(
+            scrub("pin=1234 code=a1b2c", &["1234", "a1b2c"]),
+            "pin=[REDACTED] code=[REDACTED]"
+        );
+        // ...but anything below the minimum is not a credential worth mangling text for
+        assert_eq!(scrub("region us", &["us"]), "region us");
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint("secret"), fingerprint("secret"));
+        assert_ne!(fingerprint("secret"), fingerprint("secret2"));
+        assert_eq!(fingerprint("secret").len(), 16);
+    }
+}
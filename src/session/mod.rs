@@ -1,2 +1,31 @@
+pub mod pending;
+pub mod state;
 pub mod store;
 pub mod types;
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+/// Local progress, validation state and staged credentials. The manifest next to them is meant
+/// to be committed, so only these are ignored.
+const GITIGNORE: &str = "\
+# Written by getapi: local progress and staged credentials, not for version control.
+# Keep manifest.json tracked.
+pending/
+sessions/
+state.json
+";
+
+/// Create `.getapi/` if needed, with a `.gitignore` that keeps what getapi writes there out of
+/// version control. An existing `.gitignore` is left alone.
+pub fn ensure_dir() -> Result<PathBuf> {
+    let dir = PathBuf::from(".getapi");
+    fs::create_dir_all(&dir)?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(gitignore, GITIGNORE)?;
+    }
+    Ok(dir)
+}
@@ -0,0 +1,71 @@
+//! Credentials collected by a run that hasn't finished yet.
+//!
+//! Output files are only written when a recipe completes, so values pasted before a pause or a
+//! cancel are staged here until `getapi resume` picks them up. With `--stage-credentials` they
+//! are also staged after every step. The file is readable by the current user only and removed
+//! once the run completes or fails.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::error::Result;
+
+fn pending_dir() -> PathBuf {
+    PathBuf::from(".getapi").join("pending")
+}
+
+fn pending_path(provider: &str) -> PathBuf {
+    pending_dir().join(format!("{}.json", provider))
+}
+
+/// Stage `values` for `provider`, replacing anything staged before.
+pub fn save(provider: &str, values: &HashMap<String, String>) -> Result<()> {
+    if values.is_empty() {
+        return clear(provider);
+    }
+    super::ensure_dir()?;
+    fs::create_dir_all(pending_dir())?;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(pending_path(provider))?;
+    // `mode` only applies when the file is created; tighten one left over from before too
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(serde_json::to_string_pretty(values)?.as_bytes())?;
+    Ok(())
+}
+
+/// The values staged for `provider`, if any.
+pub fn load(provider: &str) -> HashMap<String, String> {
+    fs::read_to_string(pending_path(provider))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn clear(provider: &str) -> Result<()> {
+    let path = pending_path(provider);
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+pub fn clear_all() -> Result<()> {
+    let dir = pending_dir();
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,235 @@
+//! When each saved credential last went through validation, kept in `.getapi/state.json`.
+//!
+//! Entries are keyed by provider and output key, and hold a fingerprint of the value that was
+//! checked rather than the value itself, so a changed credential can be spotted without
+//! storing it.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::recipe::deps;
+use crate::recipe::types::{Recipe, Step};
+use crate::secrets;
+use crate::validators::ValidationRecord;
+
+/// The last validation of one output key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyState {
+    pub validated_at: DateTime<Utc>,
+    pub passed: bool,
+    /// `secrets::fingerprint` of the value that was validated.
+    pub fingerprint: String,
+}
+
+/// Output key → its last validation, for one provider.
+pub type KeyStates = HashMap<String, KeyState>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct State {
+    #[serde(default)]
+    pub providers: HashMap<String, KeyStates>,
+}
+
+fn state_path() -> PathBuf {
+    PathBuf::from(".getapi").join("state.json")
+}
+
+/// The saved state, or an empty one if there is none yet.
+pub fn load() -> State {
+    fs::read_to_string(state_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(state: &State) -> Result<()> {
+    super::ensure_dir()?;
+    fs::write(state_path(), serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+pub fn clear(provider: &str) -> Result<()> {
+    let mut state = load();
+    if state.providers.remove(provider).is_some() {
+        save(&state)?;
+    }
+    Ok(())
+}
+
+pub fn clear_all() -> Result<()> {
+    let path = state_path();
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+impl State {
+    /// Record the outcome of validations run against `values`. Each record covers the keys its
+    /// step depends on. Records that couldn't reach the provider, were skipped, or ran without
+    /// all of their keys, say nothing about the credentials and are left out.
+    pub fn record(
+        &mut self,
+        recipe: &Recipe,
+        records: &[ValidationRecord],
+        values: &HashMap<String, String>,
+        at: DateTime<Utc>,
+    ) {
+        for record in records {
+            if record.network_error || record.skipped {
+                continue;
+            }
+            let step = recipe.steps.iter().find(|s| s.id() == record.step_id);
+            let Some(Step::Validate { depends_on, .. }) = step else {
+                continue;
+            };
+            let keys = deps::value_keys(recipe, depends_on);
+            if keys.is_empty() || keys.iter().any(|k| !values.contains_key(k)) {
+                continue;
+            }
+
+            let entries = self.providers.entry(recipe.id.clone()).or_default();
+            for key in keys {
+                entries.insert(
+                    key.clone(),
+                    KeyState {
+                        validated_at: at,
+                        passed: record.passed,
+                        fingerprint: secrets::fingerprint(&values[&key]),
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// What the saved state says about a provider's current credentials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    /// Every validated key still has the value that passed; gives the oldest validation.
+    Validated(DateTime<Utc>),
+    /// The last validation of at least one key failed.
+    Failed(DateTime<Utc>),
+    /// At least one key has changed since it was validated.
+    Changed,
+}
+
+/// Compare the saved entries against the credentials in `values`. `None` if no key with a
+/// value has been validated.
+pub fn freshness(entries: &KeyStates, values: &HashMap<String, String>) -> Option<Freshness> {
+    let checked: Vec<(&KeyState, &String)> = entries
+        .iter()
+        .filter_map(|(key, entry)| values.get(key).map(|value| (entry, value)))
+        .collect();
+    if checked
+        .iter()
+        .any(|(entry, value)| entry.fingerprint != secrets::fingerprint(value))
+    {
+        return Some(Freshness::Changed);
+    }
+    if let Some((entry, _)) = checked.iter().find(|(entry, _)| !entry.passed) {
+        return Some(Freshness::Failed(entry.validated_at));
+    }
+    checked
+        .iter()
+        .map(|(entry, _)| entry.validated_at)
+        .min()
+        .map(Freshness::Validated)
+}
+
+/// When `keys` last passed validation, if every one of them did so within `max_age` and still
+/// has the value that was validated. Gives the oldest of their times.
+pub fn recent_success(
+    entries: &KeyStates,
+    keys: &[String],
+    values: &HashMap<String, String>,
+    max_age: Duration,
+) -> Option<DateTime<Utc>> {
+    if keys.is_empty() {
+        return None;
+    }
+    let cutoff = Utc::now() - max_age;
+    let mut oldest: Option<DateTime<Utc>> = None;
+    for key in keys {
+        let entry = entries.get(key)?;
+        let value = values.get(key)?;
+        if !entry.passed
+            || entry.validated_at < cutoff
+            || entry.fingerprint != secrets::fingerprint(value)
+        {
+            return None;
+        }
+        oldest = Some(oldest.map_or(entry.validated_at, |t| t.min(entry.validated_at)));
+    }
+    oldest
+}
+
+/// Parse an age like `90s`, `30m`, `24h` or `7d`.
+pub fn parse_age(text: &str) -> std::result::Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: i64 = number
+        .parse()
+        .map_err(|_| format!("expected a number followed by s, m, h or d, got '{}'", text))?;
+    match unit {
+        "s" => Ok(Duration::seconds(number)),
+        "m" => Ok(Duration::minutes(number)),
+        "h" => Ok(Duration::hours(number)),
+        "d" => Ok(Duration::days(number)),
+        _ => Err(format!("unknown unit '{}': use s, m, h or d", unit)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recipe::loader;
+
+    #[test]
+    fn test_recent_success_needs_same_value() {
+        let recipe = loader::load_all_bundled()
+            .into_iter()
+            .find(|r| r.id == "openai")
+            .unwrap();
+        let values = HashMap::from([("OPENAI_API_KEY".to_string(), "sk-one".to_string())]);
+        let record = ValidationRecord {
+            step_id: "validate_key".to_string(),
+            passed: true,
+            ..Default::default()
+        };
+
+        let mut state = State::default();
+        state.record(&recipe, &[record], &values, Utc::now() - Duration::hours(2));
+        let entries = &state.providers["openai"];
+        assert!(!serde_json::to_string(&state).unwrap().contains("sk-one"));
+
+        let keys = vec!["OPENAI_API_KEY".to_string()];
+        assert!(recent_success(entries, &keys, &values, Duration::hours(24)).is_some());
+        assert!(recent_success(entries, &keys, &values, Duration::hours(1)).is_none());
+
+        let changed = HashMap::from([("OPENAI_API_KEY".to_string(), "sk-two".to_string())]);
+        assert!(recent_success(entries, &keys, &changed, Duration::hours(24)).is_none());
+        assert_eq!(freshness(entries, &changed), Some(Freshness::Changed));
+        assert!(matches!(
+            freshness(entries, &values),
+            Some(Freshness::Validated(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("24h"), Ok(Duration::hours(24)));
+        assert_eq!(parse_age("7d"), Ok(Duration::days(7)));
+        assert_eq!(parse_age("90s"), Ok(Duration::seconds(90)));
+        assert!(parse_age("soon").is_err());
+        assert!(parse_age("3w").is_err());
+    }
+}
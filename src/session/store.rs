@@ -2,7 +2,13 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::error::{GetapiError, Result};
-use crate::session::types::Session;
+use crate::frontend::Message;
+use crate::output::OutputFormat;
+use crate::recipe::types::{Recipe, Step};
+use crate::runner::context::{Checkpoint, RunContext};
+use crate::session::pending;
+use crate::session::state;
+use crate::session::types::{Progress, Session};
 
 fn sessions_dir() -> PathBuf {
     PathBuf::from(".getapi").join("sessions")
@@ -13,6 +19,7 @@ fn session_path(provider: &str) -> PathBuf {
 }
 
 pub fn save(session: &Session) -> Result<()> {
+    super::ensure_dir()?;
     let dir = sessions_dir();
     fs::create_dir_all(&dir)?;
     let path = session_path(&session.provider);
@@ -21,6 +28,97 @@ pub fn save(session: &Session) -> Result<()> {
     Ok(())
 }
 
+/// A checkpoint that saves `session` as in progress after every step. With
+/// `ctx.stage_credentials`, the credentials collected so far are staged in the pending store too.
+pub fn checkpoint(session: &Session, recipe: &Recipe) -> Checkpoint {
+    let mut session = session.clone();
+    let recipe = recipe.clone();
+    Box::new(move |ctx| {
+        session.record_progress(&progress(&recipe, ctx));
+        save(&session)?;
+        if ctx.stage_credentials && can_stage(ctx) {
+            pending::save(&recipe.id, &ctx.collected)?;
+        }
+        Ok(())
+    })
+}
+
+/// What the session keeps of `ctx`.
+fn progress<'a>(recipe: &'a Recipe, ctx: &'a RunContext) -> Progress<'a> {
+    Progress {
+        completed_steps: &ctx.completed_steps,
+        skipped_steps: &ctx.skipped_steps,
+        choices_made: &ctx.choices_made,
+        // A skipped validation leaves the step's earlier outcome alone
+        validations: ctx
+            .validations
+            .iter()
+            .filter(|v| !v.skipped)
+            .map(|v| (v.step_id.as_str(), v.passed))
+            .collect(),
+        current_step: recipe.steps.get(ctx.current_step_index).map(Step::id),
+    }
+}
+
+/// Credentials printed to stdout were never going to a file, so they aren't kept on disk either.
+fn can_stage(ctx: &RunContext) -> bool {
+    ctx.output_format != OutputFormat::Stdout
+}
+
+/// Save how far a run got, given the result of `runner::run`. A cancelled or paused run is
+/// saved as paused at the step it was on, and the credentials it collected are staged in the
+/// pending store so `getapi resume` doesn't ask for them again. A run that finished or failed
+/// leaves nothing staged. Validation results are kept in the state file either way.
+pub fn save_run(
+    session: &mut Session,
+    recipe: &Recipe,
+    ctx: &mut RunContext,
+    result: Result<()>,
+) -> Result<()> {
+    // Remember what was validated even if the run then failed
+    if !ctx.validations.is_empty() {
+        let mut validated = state::load();
+        validated.record(recipe, &ctx.validations, &ctx.collected, chrono::Utc::now());
+        if let Err(e) = state::save(&validated) {
+            ctx.frontend
+                .warning(&format!("Couldn't save validation results: {}", e));
+        }
+    }
+
+    let stopped = matches!(
+        result,
+        Err(GetapiError::UserCancelled | GetapiError::Paused)
+    );
+    if stopped {
+        ctx.paused = true;
+    } else if result.is_err() {
+        // Nothing was written, so nothing stays staged either
+        if let Err(e) = pending::clear(&recipe.id) {
+            ctx.frontend
+                .warning(&format!("Couldn't remove staged credentials: {}", e));
+        }
+        return result;
+    }
+
+    session.record_run(&progress(recipe, ctx), ctx.paused);
+    save(session)?;
+
+    if !ctx.paused || !can_stage(ctx) {
+        pending::clear(&recipe.id)?;
+    } else {
+        pending::save(&recipe.id, &ctx.collected)?;
+    }
+
+    if stopped {
+        ctx.frontend.print(Message::Blank);
+        ctx.frontend.print(Message::Pause(&format!(
+            "Progress saved. Run `getapi resume {}` to pick up where you left off.",
+            recipe.id
+        )));
+    }
+    result
+}
+
 pub fn load(provider: &str) -> Result<Session> {
     let path = session_path(provider);
     if !path.exists() {
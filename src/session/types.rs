@@ -19,12 +19,27 @@ pub struct Session {
     pub status: SessionStatus,
     pub completed_steps: Vec<String>,
     pub skipped_steps: Vec<String>,
+    /// Validate steps that failed but whose credentials were saved anyway
+    #[serde(default)]
+    pub unvalidated_steps: Vec<String>,
     pub choices_made: HashMap<String, String>,
     pub output_file: String,
     pub output_format: String,
     pub notes: Option<String>,
 }
 
+/// How far a run has got, as a session records it.
+#[derive(Debug, Clone)]
+pub struct Progress<'a> {
+    pub completed_steps: &'a [String],
+    pub skipped_steps: &'a [String],
+    pub choices_made: &'a HashMap<String, String>,
+    /// Each validate step run so far, and whether it passed
+    pub validations: Vec<(&'a str, bool)>,
+    /// The step the run is on, or stopped at
+    pub current_step: Option<&'a str>,
+}
+
 impl Session {
     pub fn new(provider: &str, output_file: &str, output_format: &str) -> Self {
         let now = chrono::Utc::now().to_rfc3339();
@@ -37,10 +52,47 @@ impl Session {
             status: SessionStatus::InProgress,
             completed_steps: Vec::new(),
             skipped_steps: Vec::new(),
+            unvalidated_steps: Vec::new(),
             choices_made: HashMap::new(),
             output_file: output_file.to_string(),
             output_format: output_format.to_string(),
             notes: None,
         }
     }
+
+    /// Record a run that's still going, after each step.
+    pub fn record_progress(&mut self, progress: &Progress) {
+        self.record_steps(progress);
+        self.status = SessionStatus::InProgress;
+        self.current_step = progress.current_step.map(str::to_string);
+    }
+
+    /// Record how far a run got: its completed steps and choices, and where it paused.
+    pub fn record_run(&mut self, progress: &Progress, paused: bool) {
+        self.record_steps(progress);
+
+        if paused {
+            self.status = SessionStatus::Paused;
+            if let Some(step) = progress.current_step {
+                self.current_step = Some(step.to_string());
+            }
+        } else {
+            self.status = SessionStatus::Completed;
+            self.current_step = None;
+        }
+    }
+
+    fn record_steps(&mut self, progress: &Progress) {
+        self.completed_steps = progress.completed_steps.to_vec();
+        self.skipped_steps = progress.skipped_steps.to_vec();
+        self.choices_made = progress.choices_made.clone();
+        // Steps that didn't run again keep their outcome from an earlier run
+        for &(step_id, passed) in &progress.validations {
+            self.unvalidated_steps.retain(|id| id != step_id);
+            if !passed {
+                self.unvalidated_steps.push(step_id.to_string());
+            }
+        }
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+    }
 }
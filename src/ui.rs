@@ -10,15 +10,69 @@ pub fn print_meta(label: &str, value: &str) {
     println!("  {} {}", style(format!("{}:", label)).dim(), value);
 }
 
-pub fn print_step_counter(current: usize, total: usize) {
+pub fn print_step_counter(current: usize, total: usize, remaining_secs: Option<u64>) {
     println!();
-    println!("  {}", style(format!("[{}/{}]", current, total)).dim());
+    match remaining_secs {
+        Some(secs) => println!(
+            "  {}",
+            style(format!(
+                "[{}/{}] · {} left",
+                current,
+                total,
+                format_duration(secs)
+            ))
+            .dim()
+        ),
+        None => println!("  {}", style(format!("[{}/{}]", current, total)).dim()),
+    }
+}
+
+/// A step id as a title: `open_keys` → "Open keys".
+pub fn step_title(id: &str) -> String {
+    let words = id.replace(['_', '-'], " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A rough, human-friendly duration: "under a minute", "about 4 min", "about 1 h 10 min".
+pub fn format_duration(secs: u64) -> String {
+    let mins = (secs + 30) / 60;
+    match mins {
+        0 => "under a minute".to_string(),
+        1..=59 => format!("about {} min", mins),
+        _ if mins.is_multiple_of(60) => format!("about {} h", mins / 60),
+        _ => format!("about {} h {} min", mins / 60, mins % 60),
+    }
+}
+
+/// How long ago something happened, for `secs` seconds ago: "just now", "5 minutes ago",
+/// "3 days ago".
+pub fn format_age(secs: u64) -> String {
+    let (count, unit) = match secs {
+        0..=59 => return "just now".to_string(),
+        60..=3_599 => (secs / 60, "minute"),
+        3_600..=86_399 => (secs / 3_600, "hour"),
+        _ => (secs / 86_400, "day"),
+    };
+    format!(
+        "{} {}{} ago",
+        count,
+        unit,
+        if count == 1 { "" } else { "s" }
+    )
 }
 
 pub fn print_info(message: &str) {
     println!("  {}", message);
 }
 
+pub fn print_action(message: &str) {
+    println!("  {} {}", style("→").cyan(), message);
+}
+
 pub fn print_url(url: &str) {
     println!("  {} {}", style("→").cyan(), style(url).underlined());
 }
@@ -46,3 +100,51 @@ pub fn print_bullet(message: &str) {
 pub fn print_pause(message: &str) {
     println!("  {} {}", style("⏸").cyan().bold(), message);
 }
+
+/// Print rows under bold headers, padding each column to its widest cell. Cells may be styled.
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(console::measure_text_width(cell));
+        }
+    }
+
+    let line = |cells: Vec<String>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| console::pad_str(cell, *width, console::Alignment::Left, None))
+            .map(|cell| cell.into_owned())
+            .collect();
+        println!("  {}", padded.join("  ").trim_end());
+    };
+    line(
+        headers
+            .iter()
+            .map(|h| style(h).bold().to_string())
+            .collect(),
+    );
+    for row in rows {
+        line(row.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_title() {
+        assert_eq!(step_title("open_keys"), "Open keys");
+        assert_eq!(step_title("create-app"), "Create app");
+        assert_eq!(step_title(""), "");
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(5), "just now");
+        assert_eq!(format_age(60), "1 minute ago");
+        assert_eq!(format_age(3 * 86_400 + 10), "3 days ago");
+    }
+}
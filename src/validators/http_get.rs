@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::error::{GetapiError, Result};
+use crate::http;
 use crate::recipe::template;
+use crate::validators;
 
 /// Generic HTTP GET validator. Fully driven by config from the recipe.
 ///
@@ -14,16 +16,20 @@ use crate::recipe::template;
 ///   with the remainder as the name and the value (template-expanded) as the header value.
 ///   E.g. `"headers.Notion-Version": "2022-06-28"` adds `Notion-Version: 2022-06-28`.
 ///
+/// - `identity_field`: Dot path into the JSON response naming the account (e.g. `user.email`)
+///
 /// If none of header_name/header_value/api_key_field are set, the request is made with no auth.
-pub fn validate(values: &HashMap<String, String>, config: &HashMap<String, String>) -> Result<()> {
+pub fn validate(
+    values: &HashMap<String, String>,
+    config: &HashMap<String, String>,
+) -> Result<Option<String>> {
     let url_template = config.get("url").ok_or_else(|| {
         GetapiError::ValidationFailed("http_get validator requires 'url' in config".to_string())
     })?;
 
     let url = template::expand(url_template, values)?;
 
-    let client = reqwest::blocking::Client::new();
-    let mut req = client.get(&url);
+    let mut req = http::Request::get(&url).secrets(values.values().cloned());
 
     if let (Some(name), Some(value_template)) =
         (config.get("header_name"), config.get("header_value"))
@@ -45,12 +51,12 @@ pub fn validate(values: &HashMap<String, String>, config: &HashMap<String, Strin
         }
     }
 
-    let resp = req.send()?;
+    let resp = http::send(&req)?;
 
-    if resp.status().is_success() {
-        Ok(())
+    if resp.is_success() {
+        Ok(validators::identity(&resp.body, config))
     } else {
-        let status = resp.status();
+        let status = resp.status_line();
         Err(GetapiError::ValidationFailed(format!(
             "HTTP GET {} returned {}",
             url, status
@@ -3,12 +3,36 @@ pub mod oauth2;
 
 use std::collections::HashMap;
 
+use serde::Serialize;
+
 use crate::error::{GetapiError, Result};
 
+/// The outcome of a single validate step.
+#[derive(Debug, Clone, Default, Serialize)]
+#[non_exhaustive]
+pub struct ValidationRecord {
+    pub step_id: String,
+    pub method: String,
+    pub passed: bool,
+    pub error: Option<String>,
+    /// Who the credentials belong to, if the provider said.
+    pub identity: Option<String>,
+    pub elapsed_ms: u64,
+    /// The provider couldn't be reached, so the credentials are neither good nor bad.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub network_error: bool,
+    /// The validator didn't run because values it depends on are missing, so this is neither
+    /// a pass nor a failure.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+}
+
 /// Validator function signature.
 /// - `values`: collected credential values (output_key → value)
 /// - `config`: arbitrary config from the recipe's validate step
-type ValidatorFn = fn(&HashMap<String, String>, &HashMap<String, String>) -> Result<()>;
+///
+/// Returns who the credentials belong to, when the response says.
+type ValidatorFn = fn(&HashMap<String, String>, &HashMap<String, String>) -> Result<Option<String>>;
 
 struct ValidatorEntry {
     name: &'static str,
@@ -26,11 +50,13 @@ const VALIDATORS: &[ValidatorEntry] = &[
     },
 ];
 
+/// Run the validator named `method` (e.g. `http_get`) against the collected `values`.
+/// Returns the identity the credentials belong to, if the provider's response reveals one.
 pub fn run(
     method: &str,
     values: &HashMap<String, String>,
     config: &HashMap<String, String>,
-) -> Result<()> {
+) -> Result<Option<String>> {
     for entry in VALIDATORS {
         if entry.name == method {
             return (entry.func)(values, config);
@@ -43,3 +69,70 @@ pub fn run(
         available.join(", "),
     ))
 }
+
+/// Fields commonly naming the account behind a token, in order of preference.
+const IDENTITY_FIELDS: &[&str] = &["login", "username", "email", "user", "name"];
+
+/// Objects that commonly wrap the account in a response body.
+const IDENTITY_WRAPPERS: &[&str] = &["user", "result", "data"];
+
+/// Who a validator response says the credentials belong to.
+///
+/// `identity_field` in the step's config is a dot path into the JSON body (e.g. `user.email`).
+/// Without one, a few common fields are tried at the top level and under common wrappers.
+pub fn identity(body: &str, config: &HashMap<String, String>) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+
+    if let Some(path) = config.get("identity_field") {
+        let value = path
+            .split('.')
+            .try_fold(&json, |value, part| value.get(part))?;
+        return match value {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Null => None,
+            other => Some(other.to_string()),
+        };
+    }
+
+    let candidates = std::iter::once(&json).chain(
+        IDENTITY_WRAPPERS
+            .iter()
+            .filter_map(|wrapper| json.get(wrapper)),
+    );
+    for object in candidates {
+        for field in IDENTITY_FIELDS {
+            if let Some(value) = object.get(field).and_then(|v| v.as_str()) {
+                if !value.is_empty() {
+                    return Some(value.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity() {
+        let none = HashMap::new();
+        assert_eq!(
+            identity(r#"{"login":"octocat","id":1}"#, &none).as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            identity(r#"{"ok":true,"result":{"username":"my_bot"}}"#, &none).as_deref(),
+            Some("my_bot")
+        );
+        assert_eq!(identity(r#"{"data":[]}"#, &none), None);
+        assert_eq!(identity("not json", &none), None);
+
+        let config = HashMap::from([("identity_field".to_string(), "account.id".to_string())]);
+        assert_eq!(
+            identity(r#"{"account":{"id":42},"login":"octocat"}"#, &config).as_deref(),
+            Some("42")
+        );
+    }
+}
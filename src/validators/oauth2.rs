@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::error::{GetapiError, Result};
+use crate::http;
 use crate::recipe::template;
+use crate::validators;
 
 /// OAuth2 Client Credentials validator.
 ///
@@ -12,7 +14,11 @@ use crate::recipe::template;
 /// - `grant_type`: Grant type string (default: "client_credentials")
 /// - `scope`: Optional scope parameter
 /// - `auth_method`: How to send credentials — "basic" (default) or "body"
-pub fn validate(values: &HashMap<String, String>, config: &HashMap<String, String>) -> Result<()> {
+/// - `identity_field`: Dot path into the token response naming the account, if it has one
+pub fn validate(
+    values: &HashMap<String, String>,
+    config: &HashMap<String, String>,
+) -> Result<Option<String>> {
     let token_url = config.get("token_url").ok_or_else(|| {
         GetapiError::ValidationFailed(
             "oauth2_client_credentials requires 'token_url' in config".to_string(),
@@ -51,10 +57,9 @@ pub fn validate(values: &HashMap<String, String>, config: &HashMap<String, Strin
         .map(|s| s.as_str())
         .unwrap_or("basic");
 
-    let client = reqwest::blocking::Client::new();
-    let mut req = client
-        .post(&token_url)
-        .header("Content-Type", "application/x-www-form-urlencoded");
+    let mut req = http::Request::post(&token_url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .secrets(values.values().cloned());
 
     let mut body = format!("grant_type={}", grant_type);
     if let Some(scope) = config.get("scope") {
@@ -72,13 +77,13 @@ pub fn validate(values: &HashMap<String, String>, config: &HashMap<String, Strin
         ));
     }
 
-    let resp = req.body(body).send()?;
+    let resp = http::send(&req.body(body))?;
 
-    if resp.status().is_success() {
-        Ok(())
+    if resp.is_success() {
+        Ok(validators::identity(&resp.body, config))
     } else {
-        let status = resp.status();
-        let body = resp.text().unwrap_or_default();
+        let status = resp.status_line();
+        let body = resp.body;
         Err(GetapiError::ValidationFailed(format!(
             "OAuth2 token request returned {}: {}",
             status, body
@@ -0,0 +1,125 @@
+//! Adapters that let the stdio JSON-RPC connection run over HTTP: answers posted by the page
+//! are read like lines from stdin, and everything the server writes is kept for the page to poll.
+
+use std::io::{Read, Write};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+/// Messages sent to the page so far, in order.
+#[derive(Default)]
+pub struct EventLog {
+    events: Mutex<Events>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct Events {
+    lines: Vec<String>,
+    /// Set once the run is over and nothing more will be written
+    done: bool,
+    /// How many lines the page has received
+    delivered: usize,
+}
+
+impl EventLog {
+    pub fn push(&self, line: String) {
+        self.events.lock().unwrap().lines.push(line);
+        self.changed.notify_all();
+    }
+
+    pub fn finish(&self) {
+        self.events.lock().unwrap().done = true;
+        self.changed.notify_all();
+    }
+
+    /// Lines after the first `since`, waiting up to `timeout` for new ones. Also returns whether
+    /// the run has finished.
+    pub fn since(&self, since: usize, timeout: Duration) -> (Vec<String>, bool) {
+        let events = self.events.lock().unwrap();
+        let (mut events, _) = self
+            .changed
+            .wait_timeout_while(events, timeout, |e| e.lines.len() <= since && !e.done)
+            .unwrap();
+
+        let lines: Vec<String> = events.lines.iter().skip(since).cloned().collect();
+        events.delivered = events.delivered.max(since + lines.len());
+        self.changed.notify_all();
+        (lines, events.done)
+    }
+
+    /// Wait until the page has received every line, or `timeout` passes.
+    pub fn wait_delivered(&self, timeout: Duration) {
+        let events = self.events.lock().unwrap();
+        let _ = self
+            .changed
+            .wait_timeout_while(events, timeout, |e| e.delivered < e.lines.len())
+            .unwrap();
+    }
+}
+
+/// `Write` end of the connection: each complete line becomes an event.
+pub struct LogWriter {
+    log: Arc<EventLog>,
+    buffer: Vec<u8>,
+}
+
+impl LogWriter {
+    pub fn new(log: Arc<EventLog>) -> Self {
+        Self {
+            log,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            self.log
+                .push(String::from_utf8_lossy(&line).trim_end().to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `Read` end of the connection, fed one message at a time by the HTTP handler.
+pub struct ChannelReader {
+    messages: Receiver<String>,
+    pending: Vec<u8>,
+}
+
+impl ChannelReader {
+    pub fn new(messages: Receiver<String>) -> Self {
+        Self {
+            messages,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() {
+            match self.messages.recv() {
+                Ok(message) => {
+                    self.pending = message.replace('\n', " ").into_bytes();
+                    self.pending.push(b'\n');
+                }
+                // The server went away: end of input
+                Err(_) => return Ok(0),
+            }
+        }
+
+        let n = buf.len().min(self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+}
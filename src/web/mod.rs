@@ -0,0 +1,228 @@
+//! `getapi web <provider>`: run a recipe from a page in the browser.
+//!
+//! The page talks to a localhost-only HTTP server that bridges it to the same JSON-RPC
+//! connection `getapi serve --stdio` uses, so recipes run through the regular runner and output
+//! writers. The URL carries a one-time token: the first page load exchanges it for a session
+//! cookie, and every other request must present that cookie.
+
+pub mod bridge;
+
+use std::cell::RefCell;
+use std::io::{BufReader, Cursor, Read};
+use std::rc::Rc;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::error::{GetapiError, Result};
+use crate::recipe::registry::RecipeRegistry;
+use crate::rpc;
+use crate::rpc::connection::{Connection, RUN_FAILED};
+use crate::rpc::types::RunParams;
+use crate::ui;
+use crate::web::bridge::{ChannelReader, EventLog, LogWriter};
+
+const PAGE: &str = include_str!("page.html");
+const COOKIE: &str = "getapi_session";
+/// How long a poll for new events waits before returning empty.
+const POLL_TIMEOUT: Duration = Duration::from_secs(20);
+/// How long to keep serving after the run, so the page can pick up the final events.
+const LINGER: Duration = Duration::from_secs(10);
+
+struct State {
+    port: u16,
+    token: String,
+    session: Mutex<Option<String>>,
+    log: Arc<EventLog>,
+    answers: Mutex<Sender<String>>,
+}
+
+/// Serve the page for `params.provider` and run the recipe with answers from the browser.
+/// Returns once the run has finished, paused or failed.
+pub fn run(registry: &RecipeRegistry, params: RunParams, open_browser: bool) -> Result<()> {
+    let server = Server::http("127.0.0.1:0").map_err(|e| GetapiError::WebServer(e.to_string()))?;
+    let server = Arc::new(server);
+    let port = server
+        .server_addr()
+        .to_ip()
+        .map(|addr| addr.port())
+        .ok_or_else(|| GetapiError::WebServer("no local address".to_string()))?;
+
+    let (tx, rx) = mpsc::channel();
+    let log = Arc::new(EventLog::default());
+    let state = Arc::new(State {
+        port,
+        token: random_hex(16)?,
+        session: Mutex::new(None),
+        log: log.clone(),
+        answers: Mutex::new(tx),
+    });
+
+    let listener = server.clone();
+    let handler_state = state.clone();
+    std::thread::spawn(move || {
+        for request in listener.incoming_requests() {
+            let state = handler_state.clone();
+            std::thread::spawn(move || handle(request, &state));
+        }
+    });
+
+    let url = format!("http://127.0.0.1:{}/?token={}", port, state.token);
+    println!();
+    ui::print_info("Continue setup in your browser:");
+    ui::print_url(&url);
+    ui::print_info("This page only works on this machine. Press Ctrl-C to stop.");
+    if open_browser {
+        let _ = open::that(&url);
+    }
+
+    let conn = Rc::new(RefCell::new(Connection::new(
+        Box::new(BufReader::new(ChannelReader::new(rx))),
+        Box::new(LogWriter::new(log.clone())),
+    )));
+    let result = rpc::run(registry, &params, &conn);
+
+    {
+        let mut conn = conn.borrow_mut();
+        match result {
+            Ok(ref summary) => conn.respond("run".into(), summary.clone())?,
+            Err(ref e) => conn.respond_error("run".into(), RUN_FAILED, &e.to_string())?,
+        }
+    }
+    log.finish();
+    log.wait_delivered(LINGER);
+    server.unblock();
+
+    let summary = result?;
+    println!();
+    if summary["paused"] == true {
+        ui::print_pause(&format!(
+            "Paused. Run `getapi resume {}` to continue.",
+            params.provider
+        ));
+    } else {
+        ui::print_success("Setup finished in the browser.");
+    }
+    Ok(())
+}
+
+fn handle(mut request: Request, state: &State) {
+    let mut body = String::new();
+    if request.method() == &Method::Post {
+        let _ = request.as_reader().read_to_string(&mut body);
+    }
+
+    let response = route(&request, &body, state)
+        .unwrap_or_else(|status| Response::from_string("").with_status_code(status));
+    // A page that navigated away mid-response isn't an error worth reporting
+    let _ = request.respond(response);
+}
+
+type Routed = std::result::Result<Response<Cursor<Vec<u8>>>, u16>;
+
+fn route(request: &Request, body: &str, state: &State) -> Routed {
+    // Refuse anything not addressed to this server by IP or localhost (DNS rebinding)
+    let host = header(request, "Host").unwrap_or_default();
+    if host != format!("127.0.0.1:{}", state.port) && host != format!("localhost:{}", state.port) {
+        return Err(403);
+    }
+
+    let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+    match (request.method(), path) {
+        (Method::Get, "/") => open_page(request, query, state),
+        (Method::Get, "/events") => {
+            authorize(request, state)?;
+            let since = param(query, "since")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0);
+            let (lines, done) = state.log.since(since, POLL_TIMEOUT);
+            let body = format!("{{\"events\":[{}],\"done\":{}}}", lines.join(","), done);
+            Ok(with_header(
+                Response::from_string(body),
+                "Content-Type",
+                "application/json",
+            ))
+        }
+        (Method::Post, "/answer") => {
+            authorize(request, state)?;
+            if serde_json::from_str::<serde_json::Value>(body).is_err() {
+                return Err(400);
+            }
+            let answers = state.answers.lock().unwrap();
+            answers.send(body.to_string()).map_err(|_| 410u16)?;
+            Ok(Response::from_string("").with_status_code(204))
+        }
+        _ => Err(404),
+    }
+}
+
+/// The first load must carry the URL token and gets a session cookie; reloads must carry the
+/// cookie. Loading the URL again from anywhere else is refused.
+fn open_page(request: &Request, query: &str, state: &State) -> Routed {
+    let mut session = state.session.lock().unwrap();
+    let page = with_header(
+        Response::from_string(PAGE),
+        "Content-Type",
+        "text/html; charset=utf-8",
+    );
+
+    match session.as_deref() {
+        Some(id) if cookie(request).as_deref() == Some(id) => Ok(page),
+        Some(_) => Err(403),
+        None if param(query, "token") == Some(state.token.as_str()) => {
+            let id = random_hex(16).map_err(|_| 500u16)?;
+            let cookie = format!("{}={}; HttpOnly; SameSite=Strict; Path=/", COOKIE, id);
+            *session = Some(id);
+            Ok(with_header(page, "Set-Cookie", &cookie))
+        }
+        None => Err(403),
+    }
+}
+
+fn authorize(request: &Request, state: &State) -> std::result::Result<(), u16> {
+    let session = state.session.lock().unwrap();
+    match (session.as_deref(), cookie(request)) {
+        (Some(id), Some(ref presented)) if id == presented => Ok(()),
+        _ => Err(403),
+    }
+}
+
+fn header(request: &Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.to_string())
+}
+
+fn cookie(request: &Request) -> Option<String> {
+    let cookies = header(request, "Cookie")?;
+    cookies
+        .split(';')
+        .filter_map(|c| c.trim().split_once('='))
+        .find(|(name, _)| *name == COOKIE)
+        .map(|(_, value)| value.to_string())
+}
+
+fn param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn with_header<R: Read>(response: Response<R>, name: &str, value: &str) -> Response<R> {
+    match Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+        Ok(header) => response.with_header(header),
+        Err(_) => response,
+    }
+}
+
+fn random_hex(len: usize) -> Result<String> {
+    let mut bytes = vec![0u8; len];
+    getrandom::getrandom(&mut bytes).map_err(|e| GetapiError::WebServer(e.to_string()))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
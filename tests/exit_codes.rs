@@ -0,0 +1,143 @@
+use std::fs;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+
+#[test]
+fn validate_exit_codes() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // Provider not found
+    cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .args(["validate", "no-such-provider"])
+        .assert()
+        .code(5);
+
+    // Nothing saved yet
+    cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .args(["validate", "openai"])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn strict_fails_on_unvalidated_credentials() {
+    let dir = tempfile::tempdir().unwrap();
+    let answers = dir.path().join("answers.json");
+    fs::write(
+        &answers,
+        r#"{ "answers": {
+            "check_billing": "Yes, billing is set up",
+            "collect_key": "sk-proj-abcdefghijklmnopqrstuvwxyz"
+        } }"#,
+    )
+    .unwrap();
+    let cassettes = dir.path().join("cassettes");
+    fs::create_dir(&cassettes).unwrap();
+
+    // A missing cassette is a fixture problem, not a failed validation
+    let output = cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .arg("openai")
+        .arg("--answers")
+        .arg(&answers)
+        .arg("--replay-http")
+        .arg(&cassettes)
+        .arg("--strict")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let expected = stderr
+        .split("(expected ")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .unwrap_or_else(|| panic!("no cassette path in: {}", stderr));
+    fs::write(
+        expected,
+        r#"{
+            "request": { "method": "GET", "url": "https://api.openai.com/v1/models", "headers": [] },
+            "response": { "status": 401, "body": "{\"error\":\"invalid_api_key\"}" }
+        }"#,
+    )
+    .unwrap();
+
+    // With the key rejected, --strict stops before writing
+    cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .arg("openai")
+        .arg("--answers")
+        .arg(&answers)
+        .arg("--replay-http")
+        .arg(&cassettes)
+        .arg("--strict")
+        .assert()
+        .code(4);
+    assert!(!dir.path().join(".env").exists());
+
+    // The replay flags work after a subcommand too
+    cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .args(["validate", "openai", "--replay-http"])
+        .arg(&cassettes)
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn failed_run_leaves_nothing_staged() {
+    let dir = tempfile::tempdir().unwrap();
+    let answers = dir.path().join("answers.json");
+    fs::write(
+        &answers,
+        r#"{ "answers": {
+            "check_billing": "Yes, billing is set up",
+            "collect_key": "sk-proj-abcdefghijklmnopqrstuvwxyz"
+        } }"#,
+    )
+    .unwrap();
+    let cassettes = dir.path().join("cassettes");
+    fs::create_dir(&cassettes).unwrap();
+
+    // The missing cassette stops the run after the key was collected
+    cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .arg("openai")
+        .arg("--answers")
+        .arg(&answers)
+        .arg("--replay-http")
+        .arg(&cassettes)
+        .arg("--strict")
+        .arg("--stage-credentials")
+        .assert()
+        .code(1);
+    assert!(!dir.path().join(".getapi/pending/openai.json").exists());
+    let gitignore = fs::read_to_string(dir.path().join(".getapi/.gitignore")).unwrap();
+    assert!(gitignore.contains("pending/"));
+}
+
+#[test]
+fn declined_confirm_pauses() {
+    let dir = tempfile::tempdir().unwrap();
+    let answers = dir.path().join("answers.json");
+    fs::write(
+        &answers,
+        r#"{ "answers": {
+            "check_billing": "No / Not sure",
+            "confirm_billing": false
+        } }"#,
+    )
+    .unwrap();
+
+    cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .arg("openai")
+        .arg("--answers")
+        .arg(&answers)
+        .assert()
+        .code(75);
+    let session = fs::read_to_string(dir.path().join(".getapi/sessions/openai.json")).unwrap();
+    assert!(session.contains(r#""status": "paused""#), "{}", session);
+    assert!(session.contains(r#""current_step": "confirm_billing""#));
+}
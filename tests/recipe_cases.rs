@@ -0,0 +1,34 @@
+use std::fs;
+use std::path::Path;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+
+/// Runs `getapi test-recipe` for every provider with cases under `tests/cases/<provider>/`.
+#[test]
+fn bundled_recipes_pass_their_cases() {
+    let cases_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/cases");
+
+    for entry in fs::read_dir(&cases_dir).unwrap() {
+        let dir = entry.unwrap().path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let provider = dir.file_name().unwrap().to_str().unwrap().to_string();
+
+        let mut cases: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        cases.sort();
+
+        cargo_bin_cmd!("getapi")
+            .arg("test-recipe")
+            .arg(&provider)
+            .arg("--case")
+            .args(&cases)
+            .assert()
+            .success();
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+
+const RECIPE: &str = r#"{
+  "schema_version": "1",
+  "id": "demo",
+  "display_name": "Demo",
+  "description": "A recipe for exercising the stdio server.",
+  "category": ["test"],
+  "website": "https://example.com",
+  "auth_types": ["api_key"],
+  "outputs": [{ "key": "DEMO_TOKEN", "description": "Token", "sensitive": true }],
+  "steps": [
+    { "id": "intro", "type": "info", "message": "Welcome." },
+    {
+      "id": "plan",
+      "type": "prompt_choice",
+      "message": "Which plan?",
+      "choices": [{ "label": "Free" }, { "label": "Paid" }]
+    },
+    { "id": "token", "type": "prompt_input", "message": "Paste your token", "output_key": "DEMO_TOKEN" }
+  ]
+}"#;
+
+/// Drives a full run over `serve --stdio`, answering prompts by their server-assigned ids.
+#[test]
+fn serve_stdio_runs_a_recipe_without_echoing_secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("recipes")).unwrap();
+    fs::write(dir.path().join("recipes/demo.json"), RECIPE).unwrap();
+
+    let secret = "demo-0123456789abcdef";
+    let input = [
+        r#"{"jsonrpc":"2.0","id":"run","method":"run","params":{"provider":"demo","output_file":"out.env"}}"#.to_string(),
+        r#"{"jsonrpc":"2.0","id":1,"result":1}"#.to_string(),
+        format!(r#"{{"jsonrpc":"2.0","id":2,"result":"{}"}}"#, secret),
+        r#"{"jsonrpc":"2.0","id":"bye","method":"shutdown"}"#.to_string(),
+    ]
+    .join("\n");
+
+    let output = cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .args(["--recipe-dir", "recipes", "serve", "--stdio"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains(secret));
+
+    let messages: Vec<Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    let methods: Vec<&str> = messages
+        .iter()
+        .filter_map(|m| m["method"].as_str())
+        .filter(|m| *m != "message")
+        .collect();
+    assert_eq!(
+        methods,
+        [
+            "step_started",
+            "step_started",
+            "prompt_choice",
+            "step_started",
+            "prompt_input",
+            "completed"
+        ]
+    );
+
+    let prompt = messages
+        .iter()
+        .find(|m| m["method"] == "prompt_input")
+        .unwrap();
+    assert_eq!(prompt["params"]["secret"], true);
+
+    let run = messages.iter().find(|m| m["id"] == "run").unwrap();
+    assert_eq!(run["result"]["collected"][0], "DEMO_TOKEN");
+
+    let written = fs::read_to_string(dir.path().join("out.env")).unwrap();
+    assert!(written.contains(&format!("DEMO_TOKEN={}", secret)));
+}
+
+/// A copy step that isn't marked sensitive, so a failed copy prints the value it was built from.
+const ENV_RECIPE: &str = r#"{
+  "schema_version": "1",
+  "id": "demo",
+  "display_name": "Demo",
+  "description": "A recipe for exercising the stdio server.",
+  "category": ["test"],
+  "website": "https://example.com",
+  "auth_types": ["api_key"],
+  "outputs": [{ "key": "DEMO_TOKEN", "description": "Token", "sensitive": true }],
+  "steps": [
+    { "id": "token", "type": "prompt_input", "message": "Paste your token", "output_key": "DEMO_TOKEN" },
+    {
+      "id": "copy",
+      "type": "copy_to_clipboard",
+      "message": "Copy the header",
+      "value": "Bearer {{DEMO_TOKEN}}",
+      "sensitive": false
+    }
+  ]
+}"#;
+
+/// Values that never went through a secret prompt are scrubbed too.
+#[test]
+fn serve_stdio_scrubs_secrets_taken_from_the_environment() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("recipes")).unwrap();
+    fs::write(dir.path().join("recipes/demo.json"), ENV_RECIPE).unwrap();
+
+    let secret = "demo-0123456789abcdef";
+    let input = [
+        r#"{"jsonrpc":"2.0","id":"run","method":"run","params":{"provider":"demo","output_file":"out.env","use_env":true}}"#,
+        r#"{"jsonrpc":"2.0","id":"bye","method":"shutdown"}"#,
+    ]
+    .join("\n");
+
+    let output = cargo_bin_cmd!("getapi")
+        .current_dir(dir.path())
+        .env("DEMO_TOKEN", secret)
+        // No display, so the copy fails and the value is printed instead
+        .env_remove("DISPLAY")
+        .env_remove("WAYLAND_DISPLAY")
+        .args(["--recipe-dir", "recipes", "serve", "--stdio"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("DEMO_TOKEN taken from environment."),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Could not copy to clipboard"), "{}", stdout);
+    assert!(!stdout.contains(secret), "{}", stdout);
+}
@@ -0,0 +1,114 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+
+const RECIPE: &str = r#"{
+  "schema_version": "1",
+  "id": "demo",
+  "display_name": "Demo",
+  "description": "A recipe for exercising the web mode.",
+  "category": ["test"],
+  "website": "https://example.com",
+  "auth_types": ["api_key"],
+  "outputs": [{ "key": "DEMO_TOKEN", "description": "Token", "sensitive": true }],
+  "steps": [
+    {
+      "id": "plan",
+      "type": "prompt_choice",
+      "message": "Which plan?",
+      "choices": [{ "label": "Free" }, { "label": "Paid" }]
+    },
+    { "id": "token", "type": "prompt_input", "message": "Paste your token", "output_key": "DEMO_TOKEN" }
+  ]
+}"#;
+
+/// Drives `getapi web` with a plain HTTP client, the way the page does.
+#[test]
+fn web_mode_runs_a_recipe_from_http_answers() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("recipes")).unwrap();
+    fs::write(dir.path().join("recipes/demo.json"), RECIPE).unwrap();
+
+    let mut child = Command::new(assert_cmd::cargo::cargo_bin!("getapi"))
+        .current_dir(dir.path())
+        .args(["--recipe-dir", "recipes", "--output-file", "out.env"])
+        .args(["web", "demo", "--no-open"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let url = loop {
+        let mut line = String::new();
+        assert!(stdout.read_line(&mut line).unwrap() > 0, "no URL printed");
+        if let Some(start) = line.find("http://") {
+            break line[start..].trim().to_string();
+        }
+    };
+    let base = url.split_once("/?").unwrap().0.to_string();
+    let client = reqwest::blocking::Client::builder()
+        .no_proxy()
+        .build()
+        .unwrap();
+
+    // The token is exchanged for a cookie on first load and can't be used again
+    let page = client.get(&url).send().unwrap();
+    assert_eq!(page.status(), 200);
+    let cookie = page.headers()["set-cookie"]
+        .to_str()
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string();
+    assert_eq!(client.get(&url).send().unwrap().status(), 403);
+    let events = format!("{}/events?since=0", base);
+    assert_eq!(client.get(&events).send().unwrap().status(), 403);
+
+    let secret = "demo-0123456789abcdef";
+    let mut since = 0;
+    let mut methods = Vec::new();
+    loop {
+        let body: Value = client
+            .get(format!("{}/events?since={}", base, since))
+            .header("Cookie", &cookie)
+            .send()
+            .unwrap()
+            .json()
+            .unwrap();
+        let batch = body["events"].as_array().unwrap();
+        since += batch.len();
+
+        for event in batch {
+            assert!(!event.to_string().contains(secret));
+            let Some(method) = event["method"].as_str() else {
+                continue;
+            };
+            methods.push(method.to_string());
+            let result = match method {
+                "prompt_choice" => json!(0),
+                "prompt_input" => json!(secret),
+                _ => continue,
+            };
+            let status = client
+                .post(format!("{}/answer", base))
+                .header("Cookie", &cookie)
+                .body(json!({ "jsonrpc": "2.0", "id": event["id"], "result": result }).to_string())
+                .send()
+                .unwrap()
+                .status();
+            assert_eq!(status, 204);
+        }
+
+        if body["done"] == true {
+            break;
+        }
+    }
+
+    assert!(child.wait().unwrap().success());
+    assert!(methods.contains(&"completed".to_string()));
+    let written = fs::read_to_string(dir.path().join("out.env")).unwrap();
+    assert!(written.contains(&format!("DEMO_TOKEN={}", secret)));
+}